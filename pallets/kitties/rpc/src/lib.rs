@@ -0,0 +1,126 @@
+//! kitties pallet的RPC接口，所有方法都以`kitties_`开头
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_kitties_runtime_api::KittiesApi as KittiesRuntimeApi;
+use pallet_kitties_runtime_api::{FamilyMember, KittyDetails, KittyId, Listing};
+
+#[rpc(client, server)]
+pub trait KittiesApi<BlockHash, AccountId, Balance, BlockNumber> {
+	#[method(name = "kitties_kitty")]
+	fn kitty(
+		&self,
+		kitty_id: KittyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<KittyDetails<AccountId, Balance, BlockNumber>>>;
+
+	#[method(name = "kitties_kittiesOf")]
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<KittyDetails<AccountId, Balance, BlockNumber>>>;
+
+	#[method(name = "kitties_listings")]
+	fn listings(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(KittyId, AccountId, Listing<Balance, BlockNumber>)>>;
+
+	#[method(name = "kitties_familyTree")]
+	fn family_tree(
+		&self,
+		kitty_id: KittyId,
+		generations: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<FamilyMember>>;
+}
+
+/// kitties RPC的实现
+pub struct Kitties<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Kitties<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// 调用runtime api出错
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(format!("{:?}", e)))).into()
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+	KittiesApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber> for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec + Send + Sync + 'static + Serialize + DeserializeOwned,
+	Balance: Codec + Send + Sync + 'static + Serialize + DeserializeOwned,
+	BlockNumber: Codec + Send + Sync + 'static + Serialize + DeserializeOwned,
+{
+	fn kitty(
+		&self,
+		kitty_id: KittyId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<KittyDetails<AccountId, Balance, BlockNumber>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.kitty(at, kitty_id)
+			.map_err(|e| runtime_error("Unable to query kitty.", e))
+	}
+
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<KittyDetails<AccountId, Balance, BlockNumber>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.kitties_of(at, owner)
+			.map_err(|e| runtime_error("Unable to query kitties of owner.", e))
+	}
+
+	fn listings(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<(KittyId, AccountId, Listing<Balance, BlockNumber>)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.listings(at)
+			.map_err(|e| runtime_error("Unable to query listings.", e))
+	}
+
+	fn family_tree(
+		&self,
+		kitty_id: KittyId,
+		generations: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<FamilyMember>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.family_tree(at, kitty_id, generations)
+			.map_err(|e| runtime_error("Unable to query family tree.", e))
+	}
+}
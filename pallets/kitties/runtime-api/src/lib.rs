@@ -0,0 +1,29 @@
+//! kitties pallet的runtime api，给前端和RPC查询kitty信息
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_kitties::{FamilyMember, KittyDetails, KittyId, Listing};
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// 根据id查询kitty
+		fn kitty(kitty_id: KittyId) -> Option<KittyDetails<AccountId, Balance, BlockNumber>>;
+
+		/// 查询owner拥有的所有kitty
+		fn kitties_of(owner: AccountId) -> Vec<KittyDetails<AccountId, Balance, BlockNumber>>;
+
+		/// 所有在售的kitty: (kitty_id, 卖家, 上架信息)
+		fn listings() -> Vec<(KittyId, AccountId, Listing<Balance, BlockNumber>)>;
+
+		/// kitty往上generations代的家族树
+		fn family_tree(kitty_id: KittyId, generations: u32) -> Vec<FamilyMember>;
+	}
+}
@@ -0,0 +1,69 @@
+use crate::*;
+use frame_support::{storage::with_storage_layer, traits::ReservableCurrency};
+use sp_runtime::{traits::Saturating, Perbill, SaturatedConversion};
+
+/// 拍卖类型
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AuctionKind<Balance> {
+	/// 英式拍卖: 出价不低于起拍价且高于当前最高价，结束时最高出价者成交
+	English { min_bid: Balance },
+	/// 荷兰式拍卖: 价格从start_price随区块线性下降到end_price，第一个接受当前价格的人成交
+	Dutch { start_price: Balance, end_price: Balance },
+}
+
+/// 拍卖信息
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub kind: AuctionKind<Balance>,
+	pub start: BlockNumber,
+	pub end: BlockNumber,
+	/// 英式拍卖当前的最高出价，出价的金额在出价者账户上被reserve
+	pub highest_bid: Option<(AccountId, Balance)>,
+}
+
+pub type AuctionOf<T> =
+	Auction<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+impl<T: Config> Pallet<T> {
+	/// 荷兰式拍卖在`now`时的价格
+	pub fn dutch_price(auction: &AuctionOf<T>, now: BlockNumberFor<T>) -> Option<BalanceOf<T>> {
+		match auction.kind {
+			AuctionKind::Dutch { start_price, end_price } => {
+				let elapsed = now.saturating_sub(auction.start).saturated_into::<u32>();
+				let duration = auction.end.saturating_sub(auction.start).saturated_into::<u32>();
+				let discount =
+					Perbill::from_rational(elapsed, duration) * start_price.saturating_sub(end_price);
+				Some(start_price.saturating_sub(discount))
+			},
+			AuctionKind::English { .. } => None,
+		}
+	}
+
+	/// 到达结束区块时结算拍卖: 最高出价者付款并获得kitty，没有出价则流拍
+	pub(crate) fn settle_auction(kitty_id: KittyId) {
+		let Some(auction) = Auctions::<T>::take(kitty_id) else { return };
+
+		if let Some((bidder, amount)) = auction.highest_bid {
+			T::Currency::unreserve(&bidder, amount);
+			// 和buy走同一条成交路径，失败时回滚成交，出价已经退回给出价者
+			let result = with_storage_layer(|| Self::do_buy(kitty_id, &auction.seller, &bidder, amount));
+			if result.is_ok() {
+				Self::deposit_event(Event::AuctionSettled {
+					kitty_id,
+					seller: auction.seller,
+					buyer: bidder,
+					price: amount,
+				});
+				return
+			}
+		}
+
+		Self::deposit_event(Event::AuctionEnded { kitty_id });
+	}
+
+	/// 将拍卖从结束区块的待结算列表中移除
+	pub(crate) fn remove_auction_schedule(kitty_id: KittyId, end: BlockNumberFor<T>) {
+		AuctionsEndingAt::<T>::mutate(end, |ids| ids.retain(|id| *id != kitty_id));
+	}
+}
@@ -0,0 +1,349 @@
+//! Benchmarking setup for pallet-kitties
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Kitties;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, Hooks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Zero};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+const NAME: [u8; 8] = *b"abcdefgh";
+
+// 给账户足够的余额，多个账户加起来也不会溢出
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 1_000u32.into());
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	fund::<T>(&caller);
+	caller
+}
+
+fn funded_account<T: Config>(name: &'static str) -> T::AccountId {
+	let who: T::AccountId = account(name, 0, SEED);
+	fund::<T>(&who);
+	who
+}
+
+fn create_kitty<T: Config>(owner: &T::AccountId) -> KittyId {
+	Kitties::<T>::do_create(owner, NAME).expect("owner has enough balance; qed")
+}
+
+// 让owner拥有count个kitty，OwnedKitties越长读写越慢
+fn fill_owned<T: Config>(owner: &T::AccountId, count: u32) {
+	for _ in 0..count {
+		create_kitty::<T>(owner);
+	}
+}
+
+// 在kitty的拍卖结束区块上放满其他拍卖
+fn fill_auction_schedule<T: Config>(end: BlockNumberFor<T>) {
+	AuctionsEndingAt::<T>::mutate(end, |ids| {
+		for id in 0..T::MaxAuctionsPerBlock::get().saturating_sub(1) {
+			let _ = ids.try_push(KittyId::MAX - id);
+		}
+	});
+}
+
+// kitty在售、出租配种并且有授权，转移时需要清理所有这些数据
+fn decorate_kitty<T: Config>(kitty_id: KittyId) {
+	let delegate: T::AccountId = account("delegate", 0, SEED);
+	KittiesOnSale::<T>::insert(kitty_id, Listing { price: T::KittyPrice::get(), expires_at: None });
+	SiresForRent::<T>::insert(kitty_id, T::KittyPrice::get());
+	KittyApprovals::<T>::insert(kitty_id, delegate);
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create() {
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&caller, T::MaxKittiesOwned::get() - 1);
+		let kitty_id = Kitties::<T>::next_kitty_id();
+
+		#[extrinsic_call]
+		create(RawOrigin::Signed(caller.clone()), NAME);
+
+		assert_eq!(Kitties::<T>::kitty_owner(kitty_id), Some(caller));
+	}
+
+	#[benchmark]
+	fn breed() {
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&caller, T::MaxKittiesOwned::get() - 3);
+		let kitty_id1 = create_kitty::<T>(&caller);
+		let kitty_id2 = create_kitty::<T>(&caller);
+		let kitty_id = Kitties::<T>::next_kitty_id();
+
+		#[extrinsic_call]
+		breed(RawOrigin::Signed(caller.clone()), kitty_id1, kitty_id2, NAME);
+
+		assert_eq!(Kitties::<T>::kitty_parents(kitty_id), Some((kitty_id1, kitty_id2)));
+	}
+
+	#[benchmark]
+	fn transfer() {
+		let caller = funded_caller::<T>();
+		let to = funded_account::<T>("to");
+		fill_owned::<T>(&caller, T::MaxKittiesOwned::get() - 1);
+		fill_owned::<T>(&to, T::MaxKittiesOwned::get() - 1);
+		let kitty_id = create_kitty::<T>(&caller);
+		decorate_kitty::<T>(kitty_id);
+
+		#[extrinsic_call]
+		transfer(RawOrigin::Signed(caller), to.clone(), kitty_id);
+
+		assert_eq!(Kitties::<T>::kitty_owner(kitty_id), Some(to));
+	}
+
+	#[benchmark]
+	fn sale() {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		#[extrinsic_call]
+		sale(RawOrigin::Signed(caller), kitty_id, T::KittyPrice::get(), Some(expires_at));
+
+		assert!(Kitties::<T>::kitties_on_sale(kitty_id).is_some());
+	}
+
+	#[benchmark]
+	fn buy() {
+		let seller = funded_account::<T>("seller");
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&seller, T::MaxKittiesOwned::get() - 1);
+		fill_owned::<T>(&caller, T::MaxKittiesOwned::get() - 1);
+		let kitty_id = create_kitty::<T>(&seller);
+		decorate_kitty::<T>(kitty_id);
+		let price = T::KittyPrice::get();
+
+		#[extrinsic_call]
+		buy(RawOrigin::Signed(caller.clone()), kitty_id, price);
+
+		assert_eq!(Kitties::<T>::kitty_owner(kitty_id), Some(caller));
+	}
+
+	#[benchmark]
+	fn cancel_sale() {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		decorate_kitty::<T>(kitty_id);
+
+		#[extrinsic_call]
+		cancel_sale(RawOrigin::Signed(caller), kitty_id);
+
+		assert!(Kitties::<T>::kitties_on_sale(kitty_id).is_none());
+	}
+
+	#[benchmark]
+	fn update_listing() {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		decorate_kitty::<T>(kitty_id);
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		#[extrinsic_call]
+		update_listing(RawOrigin::Signed(caller), kitty_id, T::KittyPrice::get(), Some(expires_at));
+
+		assert_eq!(Kitties::<T>::kitties_on_sale(kitty_id).unwrap().expires_at, Some(expires_at));
+	}
+
+	#[benchmark]
+	fn create_auction() {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		let duration = T::MaxAuctionDuration::get();
+		fill_auction_schedule::<T>(frame_system::Pallet::<T>::block_number() + duration);
+		let kind = AuctionKind::Dutch { start_price: T::KittyPrice::get(), end_price: Zero::zero() };
+
+		#[extrinsic_call]
+		create_auction(RawOrigin::Signed(caller), kitty_id, kind, duration);
+
+		assert!(Kitties::<T>::auctions(kitty_id).is_some());
+	}
+
+	// 荷兰式拍卖的出价会立即成交，比英式拍卖的出价更重
+	#[benchmark]
+	fn bid() {
+		let seller = funded_account::<T>("seller");
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&seller, T::MaxKittiesOwned::get() - 1);
+		fill_owned::<T>(&caller, T::MaxKittiesOwned::get() - 1);
+		let kitty_id = create_kitty::<T>(&seller);
+		let duration = T::MaxAuctionDuration::get();
+		fill_auction_schedule::<T>(frame_system::Pallet::<T>::block_number() + duration);
+		let price = T::KittyPrice::get();
+		let kind = AuctionKind::Dutch { start_price: price, end_price: Zero::zero() };
+		Kitties::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, kind, duration)
+			.expect("seller owns the kitty; qed");
+
+		#[extrinsic_call]
+		bid(RawOrigin::Signed(caller.clone()), kitty_id, price);
+
+		assert_eq!(Kitties::<T>::kitty_owner(kitty_id), Some(caller));
+	}
+
+	#[benchmark]
+	fn cancel_auction() {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		let duration = T::MaxAuctionDuration::get();
+		fill_auction_schedule::<T>(frame_system::Pallet::<T>::block_number() + duration);
+		let kind = AuctionKind::English { min_bid: T::KittyPrice::get() };
+		Kitties::<T>::create_auction(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			kind,
+			duration,
+		)
+		.expect("caller owns the kitty; qed");
+
+		#[extrinsic_call]
+		cancel_auction(RawOrigin::Signed(caller), kitty_id);
+
+		assert!(Kitties::<T>::auctions(kitty_id).is_none());
+	}
+
+	#[benchmark]
+	fn release() {
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&caller, T::MaxKittiesOwned::get() - 3);
+		let kitty_id1 = create_kitty::<T>(&caller);
+		let kitty_id2 = create_kitty::<T>(&caller);
+		let kitty_id = Kitties::<T>::next_kitty_id();
+		Kitties::<T>::breed(RawOrigin::Signed(caller.clone()).into(), kitty_id1, kitty_id2, NAME)
+			.expect("caller owns both parents; qed");
+		decorate_kitty::<T>(kitty_id);
+
+		#[extrinsic_call]
+		release(RawOrigin::Signed(caller), kitty_id);
+
+		assert!(Kitties::<T>::kitties(kitty_id).is_none());
+	}
+
+	#[benchmark]
+	fn offer_sire() {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+
+		#[extrinsic_call]
+		offer_sire(RawOrigin::Signed(caller), kitty_id, T::KittyPrice::get());
+
+		assert_eq!(Kitties::<T>::sire_fee(kitty_id), Some(T::KittyPrice::get()));
+	}
+
+	#[benchmark]
+	fn withdraw_sire() {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		SiresForRent::<T>::insert(kitty_id, T::KittyPrice::get());
+
+		#[extrinsic_call]
+		withdraw_sire(RawOrigin::Signed(caller), kitty_id);
+
+		assert_eq!(Kitties::<T>::sire_fee(kitty_id), None);
+	}
+
+	#[benchmark]
+	fn breed_with_sire() {
+		let sire_owner = funded_account::<T>("sire_owner");
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&caller, T::MaxKittiesOwned::get() - 2);
+		let matron_id = create_kitty::<T>(&caller);
+		let sire_id = create_kitty::<T>(&sire_owner);
+		let fee = T::KittyPrice::get();
+		SiresForRent::<T>::insert(sire_id, fee);
+		let kitty_id = Kitties::<T>::next_kitty_id();
+
+		#[extrinsic_call]
+		breed_with_sire(RawOrigin::Signed(caller.clone()), matron_id, sire_id, fee, NAME);
+
+		assert_eq!(Kitties::<T>::kitty_owner(kitty_id), Some(caller));
+	}
+
+	// operator代替owner授权，需要额外读取OperatorApprovals
+	#[benchmark]
+	fn approve() {
+		let owner = funded_account::<T>("owner");
+		let caller = funded_caller::<T>();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let kitty_id = create_kitty::<T>(&owner);
+		OperatorApprovals::<T>::insert(&owner, &caller, ());
+
+		#[extrinsic_call]
+		approve(RawOrigin::Signed(caller), kitty_id, Some(delegate.clone()));
+
+		assert_eq!(Kitties::<T>::approved(kitty_id), Some(delegate));
+	}
+
+	#[benchmark]
+	fn set_approval_for_all() {
+		let caller = funded_caller::<T>();
+		let operator: T::AccountId = account("operator", 0, SEED);
+
+		#[extrinsic_call]
+		set_approval_for_all(RawOrigin::Signed(caller.clone()), operator.clone(), true);
+
+		assert!(Kitties::<T>::is_approved_for_all(&caller, &operator));
+	}
+
+	// operator转移: 单个kitty的授权和operator授权都要读取
+	#[benchmark]
+	fn transfer_from() {
+		let owner = funded_account::<T>("owner");
+		let to = funded_account::<T>("to");
+		let caller = funded_caller::<T>();
+		fill_owned::<T>(&owner, T::MaxKittiesOwned::get() - 1);
+		fill_owned::<T>(&to, T::MaxKittiesOwned::get() - 1);
+		let kitty_id = create_kitty::<T>(&owner);
+		decorate_kitty::<T>(kitty_id);
+		OperatorApprovals::<T>::insert(&owner, &caller, ());
+
+		#[extrinsic_call]
+		transfer_from(RawOrigin::Signed(caller), owner, to.clone(), kitty_id);
+
+		assert_eq!(Kitties::<T>::kitty_owner(kitty_id), Some(to));
+	}
+
+	// 每个拍卖都有出价并且成交，是结算最重的情况
+	#[benchmark]
+	fn settle_auctions(n: Linear<0, { T::MaxAuctionsPerBlock::get() }>) {
+		let duration = T::MaxAuctionDuration::get();
+		let end = frame_system::Pallet::<T>::block_number() + duration;
+		let min_bid = T::KittyPrice::get();
+		let mut kitty_ids = Vec::new();
+		for i in 0..n {
+			let seller: T::AccountId = account("seller", i, SEED);
+			let bidder: T::AccountId = account("bidder", i, SEED);
+			fund::<T>(&seller);
+			fund::<T>(&bidder);
+			let kitty_id = create_kitty::<T>(&seller);
+			let kind = AuctionKind::English { min_bid };
+			Kitties::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, kind, duration)
+				.expect("seller owns the kitty; qed");
+			Kitties::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, min_bid)
+				.expect("bidder has enough balance; qed");
+			kitty_ids.push(kitty_id);
+		}
+
+		#[block]
+		{
+			Kitties::<T>::on_initialize(end);
+		}
+
+		for (i, kitty_id) in kitty_ids.into_iter().enumerate() {
+			let bidder: T::AccountId = account("bidder", i as u32, SEED);
+			assert_eq!(Kitties::<T>::kitty_owner(kitty_id), Some(bidder));
+		}
+	}
+
+	impl_benchmark_test_suite!(Kitties, crate::mock::new_test_ext(), crate::mock::Test);
+}
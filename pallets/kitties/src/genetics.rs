@@ -0,0 +1,114 @@
+//! kitty的基因模型
+//!
+//! 16字节的DNA被分成8个基因位点(locus)，每个位点占两个字节，分别是来自父母双方的
+//! 两个等位基因(allele)。等位基因最高位为1时是显性，否则是隐性；一个位点表现出来的性状
+//! 由显性的等位基因决定。
+//!
+//! 繁殖时孩子在每个位点上从父亲的两个等位基因中随机继承一个，从母亲的两个等位基因中
+//! 随机继承一个，每个等位基因都有`mutation_rate`的概率突变成一个随机值。
+
+use codec::Encode;
+use sp_io::hashing::blake2_256;
+use sp_runtime::Permill;
+
+pub type Dna = [u8; 16];
+
+/// 基因位点的数量
+pub const LOCI: usize = 8;
+
+/// 等位基因的显性标记位
+pub const DOMINANT_MASK: u8 = 0b1000_0000;
+
+/// 基因位点，值为位点在DNA中的序号
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Locus {
+	Fur = 0,
+	Pattern = 1,
+	EyeColor = 2,
+	EyeShape = 3,
+	Ears = 4,
+	Tail = 5,
+	Whiskers = 6,
+	Temper = 7,
+}
+
+impl Locus {
+	pub const ALL: [Locus; LOCI] = [
+		Locus::Fur,
+		Locus::Pattern,
+		Locus::EyeColor,
+		Locus::EyeShape,
+		Locus::Ears,
+		Locus::Tail,
+		Locus::Whiskers,
+		Locus::Temper,
+	];
+}
+
+/// 位点上的两个等位基因
+pub fn alleles(dna: &Dna, locus: Locus) -> (u8, u8) {
+	let index = locus as usize * 2;
+	(dna[index], dna[index + 1])
+}
+
+pub fn is_dominant(allele: u8) -> bool {
+	allele & DOMINANT_MASK != 0
+}
+
+/// 位点表现出来的性状: 显性基因优先，显隐性相同时取较大的等位基因
+pub fn expressed(dna: &Dna, locus: Locus) -> u8 {
+	let (a, b) = alleles(dna, locus);
+	match (is_dominant(a), is_dominant(b)) {
+		(true, false) => a,
+		(false, true) => b,
+		_ => a.max(b),
+	}
+}
+
+/// 由父母的DNA生成孩子的DNA
+pub fn inherit(parent1: &Dna, parent2: &Dna, seed: &[u8; 32], mutation_rate: Permill) -> Dna {
+	inherit_traced(parent1, parent2, seed, mutation_rate).0
+}
+
+/// 和`inherit`一样，同时返回哪些等位基因发生了突变: 第i位为1表示`dna[i]`是突变得到的
+pub fn inherit_traced(
+	parent1: &Dna,
+	parent2: &Dna,
+	seed: &[u8; 32],
+	mutation_rate: Permill,
+) -> (Dna, u16) {
+	let mut dna = [0u8; 16];
+	let mut mutations = 0u16;
+
+	for locus in Locus::ALL {
+		let index = locus as usize * 2;
+		for (offset, parent) in [parent1, parent2].into_iter().enumerate() {
+			let (allele, mutated) = inherit_allele(parent, locus, offset as u8, seed, mutation_rate);
+			dna[index + offset] = allele;
+			if mutated {
+				mutations |= 1 << (index + offset);
+			}
+		}
+	}
+
+	(dna, mutations)
+}
+
+fn inherit_allele(
+	parent: &Dna,
+	locus: Locus,
+	side: u8,
+	seed: &[u8; 32],
+	mutation_rate: Permill,
+) -> (u8, bool) {
+	// 每个等位基因使用独立的随机数
+	let entropy = blake2_256(&(seed, locus as u8, side).encode());
+
+	let roll = u32::from_le_bytes([entropy[1], entropy[2], entropy[3], entropy[4]]) % 1_000_000;
+	if roll < mutation_rate.deconstruct() {
+		return (entropy[5], true)
+	}
+
+	let (a, b) = alleles(parent, locus);
+	(if entropy[0] & 1 == 0 { a } else { b }, false)
+}
@@ -2,7 +2,20 @@
 
 pub use pallet::*;
 
-mod migrations;
+pub mod weights;
+pub use weights::*;
+
+mod auction;
+pub mod genetics;
+pub mod migrations;
+pub mod nonfungibles;
+mod query;
+
+pub use auction::{Auction, AuctionKind, AuctionOf};
+pub use query::{FamilyMember, KittyDetails, KittyDetailsOf, MAX_FAMILY_TREE_DEPTH};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -16,29 +29,51 @@ mod test;
 #[frame_support::pallet]
 pub mod pallet {
 	pub use frame_support::pallet_prelude::*;
-	use frame_support::traits::{Currency, ExistenceRequirement, StorageVersion, Randomness};
+	use frame_support::traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency,
+		StorageVersion,
+	};
 	use frame_support::Blake2_128Concat;
-	use frame_support::PalletId;
 	pub use frame_system::pallet_prelude::*;
 	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
-	use sp_io::hashing::blake2_128;
-	use sp_runtime::traits::AccountIdConversion;
+	use sp_io::hashing::{blake2_128, blake2_256};
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		Permill,
+	};
 
-  use crate::migrations;
+  use crate::{genetics, Auction, AuctionKind, AuctionOf, WeightInfo};
 
 	pub type KittyId = u32;
 	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
-	pub struct Kitty{
-    pub dna: [u8; 16],
-    pub name: [u8; 8],
-  }
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub struct Kitty<BlockNumber> {
+		pub dna: [u8; 16],
+		pub name: [u8; 8],
+		pub generation: u32,         // 代数，create出来的kitty是第0代
+		pub birth_block: BlockNumber, // 出生区块
+		pub last_bred: Option<BlockNumber>, // 上一次繁殖的区块
+		pub offspring: u32,          // 已经繁殖的后代数量
+	}
 
-  const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+	/// 上架信息: 卖家设定的价格以及可选的过期区块
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub struct Listing<Balance, BlockNumber> {
+		pub price: Balance,
+		pub expires_at: Option<BlockNumber>,
+	}
+
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
 
 	// 注意这里balance的type的定义
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	pub type KittyOf<T> = Kitty<BlockNumberFor<T>>;
+
+	pub type ListingOf<T> = Listing<BalanceOf<T>, BlockNumberFor<T>>;
+
 	#[pallet::config] // 模块配置
 	pub trait Config: frame_system::Config {
 		#[pallet::constant]
@@ -47,11 +82,36 @@ pub mod pallet {
 		type KittyRandomness: Randomness<Self::Hash, Self::BlockNumber>;
 
 		#[pallet::constant]
-		type KittyPrice: Get<BalanceOf<Self>>; // kitty的价格
+		type KittyPrice: Get<BalanceOf<Self>>; // 创建kitty时需要质押的token数量
 
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// 同一个区块内最多结束多少个拍卖
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+
+		/// 拍卖的最长持续区块数
+		#[pallet::constant]
+		type MaxAuctionDuration: Get<BlockNumberFor<Self>>;
+
+		/// 一个账户最多拥有多少个kitty
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+
+		/// 繁殖时每个等位基因发生突变的概率
+		#[pallet::constant]
+		type MutationRate: Get<Permill>;
 
-		type PalletId: Get<PalletId>;
+		/// 繁殖的冷却区块数，第n代kitty的冷却时间是它的(n + 1)倍
+		#[pallet::constant]
+		type BreedingCooldown: Get<BlockNumberFor<Self>>;
+
+		/// 每个kitty最多繁殖多少个后代
+		#[pallet::constant]
+		type MaxOffspring: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -64,11 +124,11 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitties)]
-	pub type Kitties<T> = StorageMap<_, Blake2_128Concat, KittyId, Kitty>;
+	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, KittyOf<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitties_on_sale)]
-	pub type KittiesOnSale<T> = StorageMap<_, Blake2_128Concat, KittyId, ()>;
+	pub type KittiesOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, ListingOf<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_owner)]
@@ -79,6 +139,58 @@ pub mod pallet {
 	pub type KittyParents<T: Config> =
 		StorageMap<_, Blake2_128Concat, KittyId, (KittyId, KittyId), OptionQuery>;
 
+	/// 每个账户拥有的kitty
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties)]
+	pub type OwnedKitties<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<KittyId, T::MaxKittiesOwned>,
+		ValueQuery,
+	>;
+
+	/// 可以出租配种的kitty以及配种费用
+	#[pallet::storage]
+	#[pallet::getter(fn sire_fee)]
+	pub type SiresForRent<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>>;
+
+	/// 被授权转移单个kitty的账户，kitty的owner变化时清除
+	#[pallet::storage]
+	#[pallet::getter(fn approved)]
+	pub type KittyApprovals<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
+
+	/// owner授权的operator，可以转移owner的所有kitty
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+	>;
+
+	/// 每个kitty在owner账户上质押的token数量，转移kitty时随之转移，release时退回
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_deposit)]
+	pub type KittyDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, AuctionOf<T>>;
+
+	/// 每个区块需要结算的拍卖
+	#[pallet::storage]
+	pub type AuctionsEndingAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<KittyId, T::MaxAuctionsPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		StorageOverflow,
@@ -90,50 +202,97 @@ pub mod pallet {
 		NotOnSale,     // 没有在售
 		AlreadyOwned,  // 已经拥有
 		NotOwned,      // 没有所有者
+		InvalidExpiry,  // 过期区块早于当前区块
+		ListingExpired, // 上架已过期
+		PriceTooHigh,   // 价格超过买家愿意支付的上限
+		InAuction,              // 正在拍卖
+		AuctionNotExist,        // 拍卖不存在
+		AuctionEnded,           // 拍卖已结束
+		InvalidAuctionDuration, // 拍卖持续时间为0或者超过上限
+		InvalidAuctionPrice,    // 荷兰式拍卖的起始价低于结束价
+		TooManyAuctions,        // 结束区块的拍卖数量已达上限
+		BidTooLow,              // 出价过低
+		AuctionHasBids,         // 已有出价的拍卖不能取消
+		TooManyKittiesOwned,    // 拥有的kitty数量已达上限
+		BreedingCooldown,       // 还在繁殖冷却中
+		TooManyOffspring,       // 后代数量已达上限
+		SireNotOffered,         // kitty没有出租配种
+		NotApproved,            // 没有被授权
+		ApproveToCaller,        // 不能授权给自己
+		DeadBeneficiary,        // 接收方账户不存在，无法承接kitty的质押
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event )]
 	pub enum Event<T: Config> {
-		KittyCreated { sender: T::AccountId, kitty_id: KittyId, kitty: Kitty },
+		KittyCreated { sender: T::AccountId, kitty_id: KittyId, kitty: KittyOf<T> },
 		KittyTransfered { sender: T::AccountId, to: T::AccountId, kitty_id: KittyId },
-		KittyOnSale { sender: T::AccountId, kitty_id: KittyId },
+		KittyOnSale {
+			sender: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+		},
+		KittyBought { buyer: T::AccountId, seller: T::AccountId, kitty_id: KittyId, price: BalanceOf<T> },
+		KittySaleCancelled { sender: T::AccountId, kitty_id: KittyId },
+		KittyListingUpdated {
+			sender: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+		},
+		AuctionCreated {
+			seller: T::AccountId,
+			kitty_id: KittyId,
+			kind: AuctionKind<BalanceOf<T>>,
+			end: BlockNumberFor<T>,
+		},
+		BidPlaced { bidder: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		AuctionSettled {
+			kitty_id: KittyId,
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			price: BalanceOf<T>,
+		},
+		AuctionEnded { kitty_id: KittyId }, // 流拍
+		AuctionCancelled { kitty_id: KittyId },
+		KittyReleased { sender: T::AccountId, kitty_id: KittyId, deposit: BalanceOf<T> },
+		SireOffered { owner: T::AccountId, kitty_id: KittyId, fee: BalanceOf<T> },
+		SireWithdrawn { owner: T::AccountId, kitty_id: KittyId },
+		SireRented {
+			sire_id: KittyId,
+			matron_id: KittyId,
+			renter: T::AccountId,
+			fee: BalanceOf<T>,
+		},
+		Approval { owner: T::AccountId, approved: Option<T::AccountId>, kitty_id: KittyId },
+		ApprovalForAll { owner: T::AccountId, operator: T::AccountId, approved: bool },
 	}
 
   #[pallet::hooks]
   impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-    fn on_runtime_upgrade() -> Weight {
-      migrations::v2::migrate::<T>();
-      Weight::zero()
+    fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+      // 结算在当前区块结束的拍卖
+      let ending = AuctionsEndingAt::<T>::take(n);
+      let count = ending.len() as u32;
+      for kitty_id in ending {
+        Self::settle_auction(kitty_id);
+      }
+      T::WeightInfo::settle_auctions(count)
     }
   }
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::create())]
 		#[pallet::call_index(0)]
 		pub fn create(origin: OriginFor<T>, name: [u8; 8]) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			let kitty_id = Self::get_next_id()?;
-			let kitty = Kitty{ dna: Self::random_value(&sender), name };
-
-			let price = T::KittyPrice::get();
-			// T::Currency::reserve(&sender, price)?; // 质押price数量的token
-			T::Currency::transfer(
-				&sender,
-				&Self::get_account_id(),
-				price,
-				ExistenceRequirement::KeepAlive,
-			)?;
-
-			Kitties::<T>::insert(kitty_id, &kitty);
-			KittyOwner::<T>::insert(kitty_id, &sender);
-
-			Self::deposit_event(Event::KittyCreated { sender, kitty_id, kitty });
+			Self::do_create(&sender, name)?;
 			Ok(())
 		}
 
-		#[pallet::weight(1)]
+		#[pallet::weight(T::WeightInfo::breed())]
 		#[pallet::call_index(1)]
 		pub fn breed(
 			origin: OriginFor<T>,
@@ -146,43 +305,15 @@ pub mod pallet {
 			// 要求两个kittyid是不一样的
 			ensure!(kitty_id1 != kitty_id2, Error::<T>::SameKittyId);
 
-			// 确定是合法的kittyId
-			ensure!(Kitties::<T>::contains_key(kitty_id1), Error::<T>::InvalidKittyId);
-			ensure!(Kitties::<T>::contains_key(kitty_id2), Error::<T>::InvalidKittyId);
-
-			let price = T::KittyPrice::get();
-			// T::Currency::reserve(&sender, price)?;
-			T::Currency::transfer(
-				&sender,
-				&Self::get_account_id(),
-				price,
-				ExistenceRequirement::KeepAlive,
-			)?;
-
-			let kitty_id = Self::get_next_id()?; // 生成新kitty的id
-			let kitty1 = Self::kitties(kitty_id1).ok_or(Error::<T>::KittyNotExist)?;
-			let kitty2 = Self::kitties(kitty_id2).ok_or(Error::<T>::KittyNotExist)?;
-
-			let selector = Self::random_value(&sender);
-			let mut data = [0u8; 16];
-			for i in 0..kitty1.dna.len() {
-				data[i] = (kitty1.dna[i] & selector[i]) | (kitty2.dna[i] & selector[i]);
-			}
-
-			let kitty = Kitty{ dna: data, name };
-			// 将kitty放入kitties中
-			Kitties::<T>::insert(kitty_id, &kitty);
-			// 更新kittyOwner
-			KittyOwner::<T>::insert(kitty_id, &sender);
-			// 更新parent信息
-			KittyParents::<T>::insert(kitty_id, (kitty_id1, kitty_id2));
-
-			Self::deposit_event(Event::KittyCreated { sender, kitty_id, kitty });
+			// 必须同时拥有父母双方
+			let owner1 = Self::kitty_owner(kitty_id1).ok_or(Error::<T>::InvalidKittyId)?;
+			let owner2 = Self::kitty_owner(kitty_id2).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner1 == sender && owner2 == sender, Error::<T>::NotOwner);
 
-			Ok(())
+			Self::do_breed(sender, kitty_id1, kitty_id2, name)
 		}
 
-		#[pallet::weight(2)]
+		#[pallet::weight(T::WeightInfo::transfer())]
 		#[pallet::call_index(2)]
 		pub fn transfer(
 			sender: OriginFor<T>,
@@ -195,15 +326,24 @@ pub mod pallet {
 			// kitty的owner是当前发起方
 			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
 			ensure!(sender == owner, Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::InAuction);
 
-			KittyOwner::<T>::insert(kitty_id, &to);
+			// 转移后原来的上架信息失效
+			if Self::do_transfer(kitty_id, &owner, &to)? {
+				Self::deposit_event(Event::KittySaleCancelled { sender: sender.clone(), kitty_id });
+			}
 			Self::deposit_event(Event::KittyTransfered { sender, to, kitty_id });
 			Ok(())
 		}
 
-		#[pallet::weight(4)]
+		#[pallet::weight(T::WeightInfo::sale())]
 		#[pallet::call_index(4)]
-		pub fn sale(sender: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+		pub fn sale(
+			sender: OriginFor<T>,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
 			let sender = ensure_signed(sender)?;
 			Self::kitties(kitty_id).ok_or(Error::<T>::KittyNotExist)?;
 			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
@@ -211,15 +351,18 @@ pub mod pallet {
 			ensure!(sender == owner, Error::<T>::NotOwner);
 
 			ensure!(Self::kitties_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::InAuction);
+
+			Self::ensure_valid_expiry(expires_at)?;
 
-			KittiesOnSale::<T>::insert(kitty_id, ());
-			Self::deposit_event(Event::KittyOnSale { sender, kitty_id });
+			KittiesOnSale::<T>::insert(kitty_id, Listing { price, expires_at });
+			Self::deposit_event(Event::KittyOnSale { sender, kitty_id, price, expires_at });
 			Ok(())
 		}
 
-		#[pallet::weight(5)]
+		#[pallet::weight(T::WeightInfo::buy())]
 		#[pallet::call_index(5)]
-		pub fn buy(sender: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+		pub fn buy(sender: OriginFor<T>, kitty_id: KittyId, max_price: BalanceOf<T>) -> DispatchResult {
 			// 购买
 			// 验证签名
 			let sender = ensure_signed(sender)?;
@@ -229,21 +372,339 @@ pub mod pallet {
 			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::NotOwned)?; // 没有owner
 			ensure!(sender != owner, Error::<T>::AlreadyOwned);
 			// 验证kitty是否在售卖
-			Self::kitties_on_sale(kitty_id).ok_or(Error::<T>::NotOnSale)?;
-			// 转移sender price数量的token到owner
-			let price = T::KittyPrice::get();
-			// T::Currency::reserve(&sender, price)?;
-			// T::Currency::unreserve(&owner, price);
-			T::Currency::transfer(&sender, &owner, price, ExistenceRequirement::KeepAlive)?;
-			// 更新kittyOwner数据
-			<KittyOwner<T>>::insert(kitty_id, &sender);
-			// 删除kitty on sale中的数据
-			<KittiesOnSale<T>>::remove(kitty_id);
+			let listing = Self::kitties_on_sale(kitty_id).ok_or(Error::<T>::NotOnSale)?;
+			// 验证上架没有过期
+			if let Some(expires_at) = listing.expires_at {
+				ensure!(
+					frame_system::Pallet::<T>::block_number() < expires_at,
+					Error::<T>::ListingExpired
+				);
+			}
+			// 防止卖家在交易打包前抬价
+			ensure!(listing.price <= max_price, Error::<T>::PriceTooHigh);
+			Self::do_buy(kitty_id, &owner, &sender, listing.price)
+		}
+
+		#[pallet::weight(T::WeightInfo::cancel_sale())]
+		#[pallet::call_index(6)]
+		pub fn cancel_sale(sender: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			// 下架
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner, Error::<T>::NotOwner);
+
+			ensure!(KittiesOnSale::<T>::take(kitty_id).is_some(), Error::<T>::NotOnSale);
+
+			Self::deposit_event(Event::KittySaleCancelled { sender, kitty_id });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::update_listing())]
+		#[pallet::call_index(7)]
+		pub fn update_listing(
+			sender: OriginFor<T>,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			// 修改上架价格和过期区块
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner, Error::<T>::NotOwner);
+
+			ensure!(KittiesOnSale::<T>::contains_key(kitty_id), Error::<T>::NotOnSale);
+			Self::ensure_valid_expiry(expires_at)?;
+
+			KittiesOnSale::<T>::insert(kitty_id, Listing { price, expires_at });
+			Self::deposit_event(Event::KittyListingUpdated { sender, kitty_id, price, expires_at });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		#[pallet::call_index(8)]
+		pub fn create_auction(
+			sender: OriginFor<T>,
+			kitty_id: KittyId,
+			kind: AuctionKind<BalanceOf<T>>,
+			duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			// 发起拍卖
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner, Error::<T>::NotOwner);
+			ensure!(Self::kitties_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::InAuction);
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+				Error::<T>::InvalidAuctionDuration
+			);
+			if let AuctionKind::Dutch { start_price, end_price } = kind {
+				ensure!(start_price >= end_price, Error::<T>::InvalidAuctionPrice);
+			}
+
+			let start = frame_system::Pallet::<T>::block_number();
+			let end = start.saturating_add(duration);
+			AuctionsEndingAt::<T>::try_mutate(end, |ids| ids.try_push(kitty_id))
+				.map_err(|_| Error::<T>::TooManyAuctions)?;
+
+			Auctions::<T>::insert(
+				kitty_id,
+				Auction { seller: sender.clone(), kind: kind.clone(), start, end, highest_bid: None },
+			);
+
+			Self::deposit_event(Event::AuctionCreated { seller: sender, kitty_id, kind, end });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[pallet::call_index(9)]
+		pub fn bid(sender: OriginFor<T>, kitty_id: KittyId, amount: BalanceOf<T>) -> DispatchResult {
+			// 出价: 英式拍卖reserve出价金额，荷兰式拍卖以当前价格立即成交(amount为愿意支付的上限)
+			let sender = ensure_signed(sender)?;
+			let mut auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotExist)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now < auction.end, Error::<T>::AuctionEnded);
+			ensure!(sender != auction.seller, Error::<T>::AlreadyOwned);
+			// 出价时就检查，否则英式拍卖结束时出价者无法接收kitty，拍卖只能流拍
+			Self::ensure_can_own(&sender)?;
+
+			match auction.kind {
+				AuctionKind::English { min_bid } => {
+					ensure!(amount >= min_bid, Error::<T>::BidTooLow);
+					if let Some((_, highest)) = &auction.highest_bid {
+						ensure!(amount > *highest, Error::<T>::BidTooLow);
+					}
+
+					T::Currency::reserve(&sender, amount)?;
+					// 退回上一个出价者的出价
+					if let Some((previous, previous_amount)) = auction.highest_bid.take() {
+						T::Currency::unreserve(&previous, previous_amount);
+					}
+					auction.highest_bid = Some((sender.clone(), amount));
+					Auctions::<T>::insert(kitty_id, auction);
+
+					Self::deposit_event(Event::BidPlaced { bidder: sender, kitty_id, amount });
+				},
+				AuctionKind::Dutch { .. } => {
+					let price = Self::dutch_price(&auction, now).ok_or(Error::<T>::AuctionNotExist)?;
+					ensure!(price <= amount, Error::<T>::PriceTooHigh);
+
+					Auctions::<T>::remove(kitty_id);
+					Self::remove_auction_schedule(kitty_id, auction.end);
+					Self::do_buy(kitty_id, &auction.seller, &sender, price)?;
+
+					Self::deposit_event(Event::AuctionSettled {
+						kitty_id,
+						seller: auction.seller,
+						buyer: sender,
+						price,
+					});
+				},
+			}
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		#[pallet::call_index(10)]
+		pub fn cancel_auction(sender: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			// 取消还没有出价的拍卖
+			let sender = ensure_signed(sender)?;
+			let auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(sender == auction.seller, Error::<T>::NotOwner);
+			ensure!(auction.highest_bid.is_none(), Error::<T>::AuctionHasBids);
+
+			Auctions::<T>::remove(kitty_id);
+			Self::remove_auction_schedule(kitty_id, auction.end);
+
+			Self::deposit_event(Event::AuctionCancelled { kitty_id });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::release())]
+		#[pallet::call_index(11)]
+		pub fn release(sender: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			// 释放kitty: 删除kitty并退回质押
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner, Error::<T>::NotOwner);
+			Self::do_release(&sender, kitty_id)
+		}
+
+		#[pallet::weight(T::WeightInfo::offer_sire())]
+		#[pallet::call_index(12)]
+		pub fn offer_sire(sender: OriginFor<T>, kitty_id: KittyId, fee: BalanceOf<T>) -> DispatchResult {
+			// 出租kitty配种，已经出租的kitty会更新费用
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner, Error::<T>::NotOwner);
+			Self::ensure_not_for_sale(kitty_id)?;
+
+			SiresForRent::<T>::insert(kitty_id, fee);
+			Self::deposit_event(Event::SireOffered { owner: sender, kitty_id, fee });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::withdraw_sire())]
+		#[pallet::call_index(13)]
+		pub fn withdraw_sire(sender: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			// 取消出租配种
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner, Error::<T>::NotOwner);
+
+			ensure!(SiresForRent::<T>::take(kitty_id).is_some(), Error::<T>::SireNotOffered);
+			Self::deposit_event(Event::SireWithdrawn { owner: sender, kitty_id });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::breed_with_sire())]
+		#[pallet::call_index(14)]
+		pub fn breed_with_sire(
+			sender: OriginFor<T>,
+			matron_id: KittyId,
+			sire_id: KittyId,
+			max_fee: BalanceOf<T>,
+			name: [u8; 8],
+		) -> DispatchResult {
+			// 租用别人的kitty配种，配种费用支付给sire的owner，新kitty属于sender
+			let sender = ensure_signed(sender)?;
+			ensure!(matron_id != sire_id, Error::<T>::SameKittyId);
+
+			let matron_owner = Self::kitty_owner(matron_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(matron_owner == sender, Error::<T>::NotOwner);
+			let sire_owner = Self::kitty_owner(sire_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sire_owner != sender, Error::<T>::AlreadyOwned);
+
+			let fee = Self::sire_fee(sire_id).ok_or(Error::<T>::SireNotOffered)?;
+			// 防止sire的owner在交易打包前提高费用
+			ensure!(fee <= max_fee, Error::<T>::PriceTooHigh);
+
+			Self::do_breed(sender.clone(), matron_id, sire_id, name)?;
+			T::Currency::transfer(&sender, &sire_owner, fee, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::SireRented { sire_id, matron_id, renter: sender, fee });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::approve())]
+		#[pallet::call_index(15)]
+		pub fn approve(
+			sender: OriginFor<T>,
+			kitty_id: KittyId,
+			delegate: Option<T::AccountId>,
+		) -> DispatchResult {
+			// 授权delegate转移kitty，delegate为None时取消授权。owner和operator都可以授权
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(
+				sender == owner || Self::is_approved_for_all(&owner, &sender),
+				Error::<T>::NotApproved
+			);
+			ensure!(delegate.as_ref() != Some(&owner), Error::<T>::ApproveToCaller);
+
+			match &delegate {
+				Some(delegate) => KittyApprovals::<T>::insert(kitty_id, delegate),
+				None => KittyApprovals::<T>::remove(kitty_id),
+			}
+			Self::deposit_event(Event::Approval { owner, approved: delegate, kitty_id });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		#[pallet::call_index(16)]
+		pub fn set_approval_for_all(
+			sender: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			// 授权或取消授权operator转移sender的所有kitty
+			let sender = ensure_signed(sender)?;
+			ensure!(sender != operator, Error::<T>::ApproveToCaller);
+
+			if approved {
+				OperatorApprovals::<T>::insert(&sender, &operator, ());
+			} else {
+				OperatorApprovals::<T>::remove(&sender, &operator);
+			}
+			Self::deposit_event(Event::ApprovalForAll { owner: sender, operator, approved });
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[pallet::call_index(17)]
+		pub fn transfer_from(
+			sender: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			kitty_id: KittyId,
+		) -> DispatchResult {
+			// 被授权的账户或者operator代替owner转移kitty
+			let sender = ensure_signed(sender)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(from == owner, Error::<T>::NotOwner);
+			ensure!(
+				sender == owner ||
+					Self::approved(kitty_id).as_ref() == Some(&sender) ||
+					Self::is_approved_for_all(&owner, &sender),
+				Error::<T>::NotApproved
+			);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::InAuction);
+
+			if Self::do_transfer(kitty_id, &owner, &to)? {
+				Self::deposit_event(Event::KittySaleCancelled { sender: owner.clone(), kitty_id });
+			}
+			Self::deposit_event(Event::KittyTransfered { sender: owner, to, kitty_id });
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		pub(crate) fn do_create(
+			sender: &T::AccountId,
+			name: [u8; 8],
+		) -> Result<KittyId, DispatchError> {
+			Self::ensure_can_own(sender)?;
+			let kitty_id = Self::get_next_id()?;
+			let kitty = Kitty {
+				dna: Self::random_value(sender),
+				name,
+				generation: 0,
+				birth_block: frame_system::Pallet::<T>::block_number(),
+				last_bred: None,
+				offspring: 0,
+			};
+
+			let price = T::KittyPrice::get();
+			T::Currency::reserve(sender, price)?; // 质押price数量的token
+
+			Kitties::<T>::insert(kitty_id, &kitty);
+			KittyOwner::<T>::insert(kitty_id, sender);
+			Self::add_owned_kitty(sender, kitty_id)?;
+			KittyDeposits::<T>::insert(kitty_id, price);
+
+			Self::deposit_event(Event::KittyCreated { sender: sender.clone(), kitty_id, kitty });
+			Ok(kitty_id)
+		}
+
+		// 删除kitty并退回质押，调用方需先校验owner
+		pub(crate) fn do_release(owner: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::InAuction);
+
+			let deposit = KittyDeposits::<T>::take(kitty_id);
+			T::Currency::unreserve(owner, deposit);
+
+			Kitties::<T>::remove(kitty_id);
+			KittyOwner::<T>::remove(kitty_id);
+			Self::remove_owned_kitty(owner, kitty_id);
+			KittyParents::<T>::remove(kitty_id);
+			KittiesOnSale::<T>::remove(kitty_id);
+			SiresForRent::<T>::remove(kitty_id);
+			KittyApprovals::<T>::remove(kitty_id);
+
+			Self::deposit_event(Event::KittyReleased { sender: owner.clone(), kitty_id, deposit });
+			Ok(())
+		}
+
 		fn get_next_id() -> Result<KittyId, DispatchError> {
 			NextKittyId::<T>::try_mutate(|next_id| -> Result<KittyId, DispatchError> {
 				let current_id = *next_id;
@@ -262,8 +723,176 @@ pub mod pallet {
 			payload.using_encoded(blake2_128)
 		}
 
-		fn get_account_id() -> T::AccountId {
-			T::PalletId::get().into_account_truncating()
+		fn random_entropy(sender: &T::AccountId) -> [u8; 32] {
+			let payload = (
+				T::KittyRandomness::random_seed(),
+				&sender,
+				<frame_system::Pallet<T>>::extrinsic_index(),
+			);
+
+			payload.using_encoded(blake2_256)
+		}
+
+		/// 成交: buyer向seller支付price，kitty转移给buyer
+		pub(crate) fn do_buy(
+			kitty_id: KittyId,
+			seller: &T::AccountId,
+			buyer: &T::AccountId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			Self::ensure_can_own(buyer)?;
+			T::Currency::transfer(buyer, seller, price, ExistenceRequirement::KeepAlive)?;
+			// 更新kittyOwner数据，同时删除kitty on sale中的数据
+			Self::do_transfer(kitty_id, seller, buyer)?;
+
+			Self::deposit_event(Event::KittyBought {
+				buyer: buyer.clone(),
+				seller: seller.clone(),
+				kitty_id,
+				price,
+			});
+			Ok(())
+		}
+
+		/// 修改kitty的owner，将质押转移到新owner，并删除原owner的上架、出租和授权信息。
+		/// 返回kitty之前是否在售。转给自己时不做任何修改
+		pub(crate) fn do_transfer(
+			kitty_id: KittyId,
+			from: &T::AccountId,
+			to: &T::AccountId,
+		) -> Result<bool, DispatchError> {
+			// 拥有数量已经达到上限时也可以转给自己
+			if from == to {
+				return Ok(false)
+			}
+			Self::ensure_can_own(to)?;
+			let deposit = Self::kitty_deposit(kitty_id);
+			if !deposit.is_zero() {
+				// 质押只能转到已经存在的账户上
+				ensure!(frame_system::Pallet::<T>::account_exists(to), Error::<T>::DeadBeneficiary);
+				T::Currency::repatriate_reserved(from, to, deposit, BalanceStatus::Reserved)?;
+			}
+			KittyOwner::<T>::insert(kitty_id, to);
+			Self::remove_owned_kitty(from, kitty_id);
+			Self::add_owned_kitty(to, kitty_id)?;
+			SiresForRent::<T>::remove(kitty_id);
+			KittyApprovals::<T>::remove(kitty_id);
+			Ok(KittiesOnSale::<T>::take(kitty_id).is_some())
+		}
+
+		/// 繁殖: 由kitty_id1和kitty_id2生成一个属于sender的新kitty，调用方负责检查权限
+		fn do_breed(
+			sender: T::AccountId,
+			kitty_id1: KittyId,
+			kitty_id2: KittyId,
+			name: [u8; 8],
+		) -> DispatchResult {
+			// 确定是合法的kittyId
+			let mut kitty1 = Self::kitties(kitty_id1).ok_or(Error::<T>::InvalidKittyId)?;
+			let mut kitty2 = Self::kitties(kitty_id2).ok_or(Error::<T>::InvalidKittyId)?;
+			Self::ensure_not_for_sale(kitty_id1)?;
+			Self::ensure_not_for_sale(kitty_id2)?;
+			Self::ensure_can_own(&sender)?;
+
+			// 父母都不在冷却中，且后代数量没有达到上限
+			let now = frame_system::Pallet::<T>::block_number();
+			Self::ensure_can_breed(&kitty1, now)?;
+			Self::ensure_can_breed(&kitty2, now)?;
+
+			let price = T::KittyPrice::get();
+			T::Currency::reserve(&sender, price)?;
+
+			let kitty_id = Self::get_next_id()?; // 生成新kitty的id
+
+			let dna = genetics::inherit(
+				&kitty1.dna,
+				&kitty2.dna,
+				&Self::random_entropy(&sender),
+				T::MutationRate::get(),
+			);
+
+			let kitty = Kitty {
+				dna,
+				name,
+				generation: kitty1.generation.max(kitty2.generation).saturating_add(1),
+				birth_block: now,
+				last_bred: None,
+				offspring: 0,
+			};
+			// 将kitty放入kitties中
+			Kitties::<T>::insert(kitty_id, &kitty);
+			// 更新父母的繁殖信息
+			for (parent_id, parent) in [(kitty_id1, &mut kitty1), (kitty_id2, &mut kitty2)] {
+				parent.last_bred = Some(now);
+				parent.offspring = parent.offspring.saturating_add(1);
+				Kitties::<T>::insert(parent_id, &*parent);
+			}
+			// 更新kittyOwner
+			KittyOwner::<T>::insert(kitty_id, &sender);
+			Self::add_owned_kitty(&sender, kitty_id)?;
+			KittyDeposits::<T>::insert(kitty_id, price);
+			// 更新parent信息
+			KittyParents::<T>::insert(kitty_id, (kitty_id1, kitty_id2));
+
+			Self::deposit_event(Event::KittyCreated { sender, kitty_id, kitty });
+
+			Ok(())
+		}
+
+		/// kitty下一次可以繁殖的区块，代数越高冷却时间越长
+		pub fn next_breedable_block(kitty: &KittyOf<T>) -> Option<BlockNumberFor<T>> {
+			let cooldown =
+				T::BreedingCooldown::get().saturating_mul(kitty.generation.saturating_add(1).into());
+			kitty.last_bred.map(|last_bred| last_bred.saturating_add(cooldown))
+		}
+
+		fn ensure_can_breed(kitty: &KittyOf<T>, now: BlockNumberFor<T>) -> DispatchResult {
+			ensure!(kitty.offspring < T::MaxOffspring::get(), Error::<T>::TooManyOffspring);
+			if let Some(breedable) = Self::next_breedable_block(kitty) {
+				ensure!(now >= breedable, Error::<T>::BreedingCooldown);
+			}
+			Ok(())
+		}
+
+		/// operator是否被owner授权转移所有kitty
+		pub fn is_approved_for_all(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+			OperatorApprovals::<T>::contains_key(owner, operator)
+		}
+
+		/// 在售或者正在拍卖的kitty不能用来繁殖或者出租配种
+		fn ensure_not_for_sale(kitty_id: KittyId) -> DispatchResult {
+			ensure!(Self::kitties_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::InAuction);
+			Ok(())
+		}
+
+		/// 账户拥有的kitty数量没有达到上限
+		fn ensure_can_own(owner: &T::AccountId) -> DispatchResult {
+			ensure!(
+				(Self::owned_kitties(owner).len() as u32) < T::MaxKittiesOwned::get(),
+				Error::<T>::TooManyKittiesOwned
+			);
+			Ok(())
+		}
+
+		fn add_owned_kitty(owner: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			OwnedKitties::<T>::try_mutate(owner, |ids| ids.try_push(kitty_id))
+				.map_err(|_| Error::<T>::TooManyKittiesOwned.into())
+		}
+
+		fn remove_owned_kitty(owner: &T::AccountId, kitty_id: KittyId) {
+			OwnedKitties::<T>::mutate(owner, |ids| ids.retain(|id| *id != kitty_id));
+		}
+
+		/// 过期区块必须在当前区块之后
+		fn ensure_valid_expiry(expires_at: Option<BlockNumberFor<T>>) -> DispatchResult {
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+			Ok(())
 		}
 	}
 }
@@ -1,2 +1,22 @@
-// pub mod v1;
-pub mod v2;
\ No newline at end of file
+//! kitties的存储迁移
+//!
+//! 每次升级存储格式都增加一个`MigrateToVn`，它只在链上版本是上一个版本时执行，执行完写入
+//! 新的存储版本，所以重复执行是安全的。`Migrations`按顺序包含了所有的迁移，可以把任意旧版本的
+//! 数据升级到当前版本。
+
+pub mod v2;
+pub mod v3;
+pub mod v4;
+pub mod v5;
+pub mod v6;
+
+pub(crate) const LOG_TARGET: &str = "runtime::kitties";
+
+/// 从v0或v1依次升级到当前版本，OldPalletId是旧版本保存质押的pallet账户
+pub type Migrations<T, OldPalletId> = (
+	v2::MigrateToV2<T>,
+	v3::MigrateToV3<T>,
+	v4::MigrateToV4<T>,
+	v5::MigrateToV5<T>,
+	v6::MigrateToV6<T, OldPalletId>,
+);
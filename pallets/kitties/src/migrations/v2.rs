@@ -1,80 +1,118 @@
+use super::LOG_TARGET;
 use crate::*;
-use frame_support::{migration::storage_key_iter, Blake2_128Concat};
 use frame_support::{
-	pallet_prelude::*, storage::StoragePrefixedMap, traits::GetStorageVersion, weights::Weight,
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
 };
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
-pub fn migrate<T: Config>() -> Weight {
-	let on_chain_version = Pallet::<T>::on_chain_storage_version();
-	let current_version = Pallet::<T>::current_storage_version();
-
-	if on_chain_version == 0 {
-    return v0_v2::<T>();
-	}
-
-  if on_chain_version == 1 {
-    return v1_v2::<T>();
-  }
-
-	Weight::zero()
-}
-
+/// v0版本的kitty，只有dna
 #[derive(Encode, Decode, Clone, Debug, TypeInfo, MaxEncodedLen, PartialEq, Eq)]
 pub struct v0_Kitty(pub [u8; 16]);
 
+/// v1版本的kitty，名字只有4个字节
 #[derive(Encode, Decode, Clone, Debug, TypeInfo, MaxEncodedLen, PartialEq, Eq)]
 pub struct v1_Kitty{ pub dna: [u8; 16], pub name: [u8; 4] }
 
-pub fn v0_v2<T: Config>() -> Weight {
-  let on_chain_version = Pallet::<T>::on_chain_storage_version();
-	let current_version = Pallet::<T>::current_storage_version();
-
-	if on_chain_version != 0 {
-		return Weight::zero();
-	}
+// v2版本的kitty，之后的版本在v4中由这个结构迁移
+#[derive(Encode, Decode, Clone, Debug, TypeInfo, MaxEncodedLen, PartialEq, Eq)]
+pub struct v2_Kitty{ pub dna: [u8; 16], pub name: [u8; 8] }
 
-	if current_version != 2 {
-		return Weight::zero();
-	}
-  let module = Kitties::<T>::module_prefix();
-	let item = Kitties::<T>::storage_prefix();
+/// v2格式的Kitties存储，v4之前用它读写kitty
+#[frame_support::storage_alias]
+pub type Kitties<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, KittyId, v2_Kitty>;
 
-	for (index, kitty) in storage_key_iter::<KittyId, v0_Kitty, Blake2_128Concat>(module, item).drain() {
+/// v0的kitty没有名字，迁移后名字全是0
+pub const UNNAMED: [u8; 8] = [0; 8];
 
-		let newKitty = Kitty {
-			// 将oldKitty的数据移植到new kitty上
-			dna: kitty.0,
-			name: *b"abcdefgh",
-		};
-    Kitties::<T>::insert(index, newKitty);
+impl From<v0_Kitty> for v2_Kitty {
+	fn from(kitty: v0_Kitty) -> Self {
+		v2_Kitty { dna: kitty.0, name: UNNAMED }
 	}
-
-	Weight::zero()
 }
 
-pub fn v1_v2<T: Config>() -> Weight {
-  let on_chain_version = Pallet::<T>::on_chain_storage_version();
-	let current_version = Pallet::<T>::current_storage_version();
-
-	if on_chain_version != 1 {
-		return Weight::zero();
+impl From<v1_Kitty> for v2_Kitty {
+	fn from(kitty: v1_Kitty) -> Self {
+		// 保留原来的4个字节名字，后面补0
+		let mut name = UNNAMED;
+		name[..4].copy_from_slice(&kitty.name);
+		v2_Kitty { dna: kitty.dna, name }
 	}
+}
 
-	if current_version != 2 {
-		return Weight::zero();
+/// 把v0或者v1的kitty迁移成v2的格式: 名字扩展到8个字节
+pub struct MigrateToV2<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version > 1 {
+			log::info!(target: LOG_TARGET, "skipping v2 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count = 0u64;
+		if on_chain_version == 0 {
+			Kitties::<T>::translate::<v0_Kitty, _>(|_, kitty| {
+				count += 1;
+				Some(kitty.into())
+			});
+		} else {
+			Kitties::<T>::translate::<v1_Kitty, _>(|_, kitty| {
+				count += 1;
+				Some(kitty.into())
+			});
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "migrated {} kitties to v2", count);
+
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
 	}
 
-  let module = Kitties::<T>::module_prefix();
-	let item = Kitties::<T>::storage_prefix();
-
-  for (index, kitty) in storage_key_iter::<KittyId, v1_Kitty, Blake2_128Concat>(module, item).drain() {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		// 记录迁移前每个kitty的dna，迁移后检查没有丢失或改变
+		let dnas: Option<BTreeMap<KittyId, [u8; 16]>> =
+			match Pallet::<T>::on_chain_storage_version() {
+				version if version == 0 => Some(
+					Kitties::<T>::iter_keys()
+						.filter_map(|id| old_kitty::<T, v0_Kitty>(id).map(|kitty| (id, kitty.0)))
+						.collect(),
+				),
+				version if version == 1 => Some(
+					Kitties::<T>::iter_keys()
+						.filter_map(|id| old_kitty::<T, v1_Kitty>(id).map(|kitty| (id, kitty.dna)))
+						.collect(),
+				),
+				_ => None,
+			};
+		Ok(dnas.encode())
+	}
 
-		let newKitty = Kitty {
-			dna: kitty.dna,
-			name: *b"abcdefgh",
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let dnas: Option<BTreeMap<KittyId, [u8; 16]>> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v2: invalid pre-upgrade state")?;
+		let dnas = match dnas {
+			Some(dnas) => dnas,
+			None => return Ok(()),
 		};
-    Kitties::<T>::insert(index, newKitty);
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 2, "v2: storage version not updated");
+		let migrated: BTreeMap<KittyId, [u8; 16]> =
+			Kitties::<T>::iter().map(|(id, kitty)| (id, kitty.dna)).collect();
+		ensure!(migrated.len() == dnas.len(), "v2: kitty count changed");
+		ensure!(migrated == dnas, "v2: kitty dna changed");
+		Ok(())
 	}
+}
 
-	Weight::zero()
-}
\ No newline at end of file
+// 按旧的格式读取一个kitty
+#[cfg(feature = "try-runtime")]
+fn old_kitty<T: Config, Old: Decode>(kitty_id: KittyId) -> Option<Old> {
+	frame_support::storage::unhashed::get(&Kitties::<T>::hashed_key_for(kitty_id))
+}
@@ -0,0 +1,100 @@
+use super::LOG_TARGET;
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, vec::Vec};
+
+/// 根据已有的KittyOwner数据建立OwnedKitties索引
+///
+/// 有owner的kitty超过MaxKittiesOwned时无法建立完整的索引，这时不做任何修改，链上版本保持2，
+/// 需要调大MaxKittiesOwned之后再升级
+pub struct MigrateToV3<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 2 {
+			log::info!(target: LOG_TARGET, "skipping v3 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count = 0u64;
+		let mut owned: BTreeMap<T::AccountId, Vec<KittyId>> = BTreeMap::new();
+		for (kitty_id, owner) in KittyOwner::<T>::iter() {
+			owned.entry(owner).or_default().push(kitty_id);
+			count += 1;
+		}
+
+		// 先转换所有的索引，任何一个owner超过上限都不写入
+		let indexes = owned
+			.into_iter()
+			.map(|(owner, ids)| BoundedVec::try_from(ids).map(|ids| (owner, ids)))
+			.collect::<Result<Vec<(T::AccountId, BoundedVec<KittyId, T::MaxKittiesOwned>)>, _>>();
+		let indexes = match indexes {
+			Ok(indexes) => indexes,
+			Err(_) => {
+				log::error!(
+					target: LOG_TARGET,
+					"v3 migration not applied, an owner has more than MaxKittiesOwned kitties"
+				);
+				return T::DbWeight::get().reads(count + 1)
+			},
+		};
+
+		let owners = indexes.len() as u64;
+		for (owner, ids) in indexes {
+			OwnedKitties::<T>::insert(owner, ids);
+		}
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "indexed {} kitties in v3", count);
+
+		// 版本和KittyOwner的读取，OwnedKitties和版本的写入
+		T::DbWeight::get().reads_writes(count + 1, owners + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		if Pallet::<T>::on_chain_storage_version() != 2 {
+			return Ok(None::<u32>.encode())
+		}
+
+		let mut owned: BTreeMap<T::AccountId, u32> = BTreeMap::new();
+		for owner in KittyOwner::<T>::iter_values() {
+			*owned.entry(owner).or_default() += 1;
+		}
+		ensure!(
+			owned.values().all(|count| *count <= T::MaxKittiesOwned::get()),
+			"v3: an owner has more than MaxKittiesOwned kitties"
+		);
+		Ok(Some(owned.values().sum::<u32>()).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let count: Option<u32> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v3: invalid pre-upgrade state")?;
+		let count = match count {
+			Some(count) => count,
+			None => return Ok(()),
+		};
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 3, "v3: storage version not updated");
+		ensure!(KittyOwner::<T>::iter_keys().count() as u32 == count, "v3: kitty count changed");
+		let mut indexed = 0u32;
+		for (owner, ids) in OwnedKitties::<T>::iter() {
+			for kitty_id in ids.iter() {
+				ensure!(
+					KittyOwner::<T>::get(kitty_id).as_ref() == Some(&owner),
+					"v3: kitty indexed under the wrong owner"
+				);
+			}
+			indexed += ids.len() as u32;
+		}
+		ensure!(indexed == count, "v3: kitties missing from the index");
+		Ok(())
+	}
+}
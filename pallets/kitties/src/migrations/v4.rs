@@ -0,0 +1,111 @@
+use super::{
+	v2::{self, v2_Kitty},
+	LOG_TARGET,
+};
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// 给kitty增加代数、出生区块和繁殖信息
+pub struct MigrateToV4<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 3 {
+			log::info!(target: LOG_TARGET, "skipping v4 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		// 之前没有记录出生区块，用升级时的区块代替
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut count = 0u64;
+		Kitties::<T>::translate::<v2_Kitty, _>(|_, kitty| {
+			count += 1;
+			Some(Kitty {
+				dna: kitty.dna,
+				name: kitty.name,
+				generation: 0,
+				birth_block: now,
+				last_bred: None,
+				offspring: 0,
+			})
+		});
+
+		// 根据KittyParents计算代数和后代数量。孩子的id总是大于父母的id，所以按id从小到大计算
+		let families: BTreeMap<KittyId, (KittyId, KittyId)> = KittyParents::<T>::iter().collect();
+		for (kitty_id, (parent1, parent2)) in families.iter() {
+			let generation = [*parent1, *parent2]
+				.iter()
+				.filter_map(|parent| Kitties::<T>::get(parent).map(|kitty| kitty.generation))
+				.max()
+				.map_or(0, |generation| generation.saturating_add(1));
+			Kitties::<T>::mutate(kitty_id, |kitty| {
+				if let Some(kitty) = kitty {
+					kitty.generation = generation;
+				}
+			});
+			for parent in [parent1, parent2] {
+				Kitties::<T>::mutate(parent, |kitty| {
+					if let Some(kitty) = kitty {
+						kitty.offspring = kitty.offspring.saturating_add(1);
+					}
+				});
+			}
+		}
+
+		StorageVersion::new(4).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "migrated {} kitties to v4", count);
+
+		// 每个有父母的kitty: 读KittyParents、两个父母和自己，写自己和两个父母
+		let families = families.len() as u64;
+		T::DbWeight::get().reads_writes(count + families * 4 + 1, count + families * 3 + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let kitties: Option<BTreeMap<KittyId, v2_Kitty>> =
+			if Pallet::<T>::on_chain_storage_version() == 3 {
+				Some(v2::Kitties::<T>::iter().collect())
+			} else {
+				None
+			};
+		Ok(kitties.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let kitties: Option<BTreeMap<KittyId, v2_Kitty>> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v4: invalid pre-upgrade state")?;
+		let kitties = match kitties {
+			Some(kitties) => kitties,
+			None => return Ok(()),
+		};
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 4, "v4: storage version not updated");
+		ensure!(
+			Kitties::<T>::iter_keys().count() == kitties.len(),
+			"v4: kitty count changed"
+		);
+		for (kitty_id, old) in kitties {
+			let kitty = Kitties::<T>::get(kitty_id).ok_or("v4: kitty lost")?;
+			ensure!(kitty.dna == old.dna && kitty.name == old.name, "v4: kitty dna or name changed");
+			if let Some((parent1, parent2)) = KittyParents::<T>::get(kitty_id) {
+				let parents_generation = [parent1, parent2]
+					.iter()
+					.filter_map(|parent| Kitties::<T>::get(parent).map(|kitty| kitty.generation))
+					.max();
+				if let Some(parents_generation) = parents_generation {
+					ensure!(kitty.generation > parents_generation, "v4: invalid generation");
+				}
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,68 @@
+use super::LOG_TARGET;
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+/// 把旧的上架信息`()`转换成`Listing`
+///
+/// 旧版本的kitty都以KittyPrice的价格出售，转换后价格不变，也不会过期
+pub struct MigrateToV5<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 4 {
+			log::info!(target: LOG_TARGET, "skipping v5 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let price = T::KittyPrice::get();
+		let mut count = 0u64;
+		KittiesOnSale::<T>::translate::<(), _>(|_, ()| {
+			count += 1;
+			Some(Listing { price, expires_at: None })
+		});
+
+		StorageVersion::new(5).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "migrated {} listings to v5", count);
+
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let on_sale: Option<BTreeSet<KittyId>> = if Pallet::<T>::on_chain_storage_version() == 4 {
+			Some(KittiesOnSale::<T>::iter_keys().collect())
+		} else {
+			None
+		};
+		Ok(on_sale.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let on_sale: Option<BTreeSet<KittyId>> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v5: invalid pre-upgrade state")?;
+		let on_sale = match on_sale {
+			Some(on_sale) => on_sale,
+			None => return Ok(()),
+		};
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 5, "v5: storage version not updated");
+		// iter会跳过无法解码的上架信息
+		let migrated: BTreeSet<KittyId> = KittiesOnSale::<T>::iter()
+			.map(|(kitty_id, listing)| {
+				ensure!(listing.price == T::KittyPrice::get(), "v5: listing price changed");
+				Ok(kitty_id)
+			})
+			.collect::<Result<_, &'static str>>()?;
+		ensure!(migrated == on_sale, "v5: listings lost");
+		Ok(())
+	}
+}
@@ -0,0 +1,125 @@
+use super::LOG_TARGET;
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	storage::with_storage_layer,
+	traits::{
+		Currency, ExistenceRequirement, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency,
+		StorageVersion,
+	},
+	weights::Weight,
+	PalletId,
+};
+use sp_runtime::traits::{AccountIdConversion, Zero};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use {
+	sp_runtime::traits::Saturating,
+	sp_std::{collections::btree_map::BTreeMap, vec::Vec},
+};
+
+/// 把旧版本付给pallet账户的质押退回owner并在owner账户上reserve，记录到KittyDeposits
+///
+/// 旧版本创建kitty时把KittyPrice转入`OldPalletId`对应的账户。pallet账户的余额按kitty逐个转给
+/// owner，余额不够时只转剩下的部分，之后pallet账户里剩余的余额留在原处。
+pub struct MigrateToV6<T, OldPalletId>(PhantomData<(T, OldPalletId)>);
+
+impl<T: Config, OldPalletId: Get<PalletId>> OnRuntimeUpgrade for MigrateToV6<T, OldPalletId> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 5 {
+			log::info!(target: LOG_TARGET, "skipping v6 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let pallet_account: T::AccountId = OldPalletId::get().into_account_truncating();
+		let price = T::KittyPrice::get();
+		let mut count = 0u64;
+		let mut missing = 0u64;
+		for (kitty_id, owner) in KittyOwner::<T>::iter() {
+			count += 1;
+			if KittyDeposits::<T>::contains_key(kitty_id) {
+				continue
+			}
+			let deposit = price.min(T::Currency::free_balance(&pallet_account));
+			// 转账和reserve要么都成功，要么都回滚
+			let moved = !deposit.is_zero() &&
+				with_storage_layer(|| -> DispatchResult {
+					T::Currency::transfer(
+						&pallet_account,
+						&owner,
+						deposit,
+						ExistenceRequirement::AllowDeath,
+					)?;
+					T::Currency::reserve(&owner, deposit)
+				})
+				.is_ok();
+			if moved {
+				KittyDeposits::<T>::insert(kitty_id, deposit);
+			} else {
+				// 没有质押的kitty记录为0，转移和release时都不会移动余额
+				KittyDeposits::<T>::insert(kitty_id, BalanceOf::<T>::zero());
+				missing += 1;
+			}
+		}
+
+		let remaining = T::Currency::free_balance(&pallet_account);
+		if !remaining.is_zero() {
+			log::warn!(target: LOG_TARGET, "{:?} left in the old kitties account", remaining);
+		}
+		if missing > 0 {
+			log::warn!(target: LOG_TARGET, "{} kitties migrated without a deposit", missing);
+		}
+
+		StorageVersion::new(6).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "recorded deposits of {} kitties in v6", count);
+
+		// 每个kitty: 读KittyOwner、KittyDeposits和两个账户，写KittyDeposits和两个账户
+		T::DbWeight::get().reads_writes(count * 4 + 2, count * 3 + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let state: Option<(u32, BalanceOf<T>)> = if Pallet::<T>::on_chain_storage_version() == 5 {
+			let pallet_account: T::AccountId = OldPalletId::get().into_account_truncating();
+			let kitties = KittyOwner::<T>::iter_keys().count() as u32;
+			Some((kitties, T::Currency::total_balance(&pallet_account)))
+		} else {
+			None
+		};
+		Ok(state.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let state: Option<(u32, BalanceOf<T>)> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v6: invalid pre-upgrade state")?;
+		let (kitties, pallet_balance) = match state {
+			Some(state) => state,
+			None => return Ok(()),
+		};
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 6, "v6: storage version not updated");
+		ensure!(KittyOwner::<T>::iter_keys().count() as u32 == kitties, "v6: kitty count changed");
+		let mut recorded = BalanceOf::<T>::zero();
+		let mut owned: BTreeMap<T::AccountId, BalanceOf<T>> = BTreeMap::new();
+		for (kitty_id, owner) in KittyOwner::<T>::iter() {
+			ensure!(KittyDeposits::<T>::contains_key(kitty_id), "v6: kitty without deposit record");
+			let deposit = KittyDeposits::<T>::get(kitty_id);
+			let total = owned.entry(owner).or_insert_with(Zero::zero);
+			*total = total.saturating_add(deposit);
+			recorded = recorded.saturating_add(deposit);
+		}
+		// 一个owner的所有kitty的质押都要reserve
+		for (owner, total) in owned {
+			ensure!(T::Currency::reserved_balance(&owner) >= total, "v6: deposit not reserved");
+		}
+		// 记录的质押都来自pallet账户
+		let pallet_account: T::AccountId = OldPalletId::get().into_account_truncating();
+		ensure!(
+			T::Currency::total_balance(&pallet_account).saturating_add(recorded) <= pallet_balance,
+			"v6: deposits not taken from the old kitties account"
+		);
+		Ok(())
+	}
+}
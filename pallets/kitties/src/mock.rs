@@ -1,11 +1,12 @@
 
 use crate as pallet_kitties;
 use frame_support::traits::{ConstU16, ConstU128, ConstU64, ConstU32};
-use frame_support::{ PalletId, parameter_types };
+use frame_support::{parameter_types, PalletId};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -76,6 +77,7 @@ impl pallet_balances::Config for Test {
 
 parameter_types!{
   pub KittyPrice: u128 = EXISTENTIAL_DEPOSIT * 10;
+  pub MutationRate: Permill = Permill::from_percent(1);
   pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
 }
 
@@ -85,7 +87,13 @@ impl pallet_kitties::Config for Test {
   type KittyRandomness = Randomness;
   type KittyPrice = KittyPrice;
   type Currency = Balances;
-  type PalletId = KittyPalletId;
+  type MaxAuctionsPerBlock = ConstU32<2>;
+  type MaxAuctionDuration = ConstU64<100>;
+  type MaxKittiesOwned = ConstU32<5>;
+  type MutationRate = MutationRate;
+  type BreedingCooldown = ConstU64<10>;
+  type MaxOffspring = ConstU32<3>;
+  type WeightInfo = ();
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Test {}
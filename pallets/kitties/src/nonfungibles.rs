@@ -0,0 +1,90 @@
+//! 实现frame_support的nonfungibles_v2 traits，方便其他pallet把kitty当作通用NFT使用。
+//! 所有kitty属于同一个collection(`()`)，item即KittyId。
+use crate::*;
+use frame_support::traits::tokens::nonfungibles_v2::{Inspect, Mutate, Transfer};
+use sp_std::vec::Vec;
+
+/// 通过`Inspect::attribute`可以读取的属性
+pub const DNA_KEY: &[u8] = b"dna";
+pub const NAME_KEY: &[u8] = b"name";
+/// SCALE编码的`(KittyId, KittyId)`，只有繁殖出来的kitty才有
+pub const PARENTS_KEY: &[u8] = b"parents";
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = KittyId;
+	type CollectionId = ();
+
+	fn owner(_collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId> {
+		Self::kitty_owner(item)
+	}
+
+	fn attribute(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		let kitty = Self::kitties(item)?;
+		match key {
+			DNA_KEY => Some(kitty.dna.to_vec()),
+			NAME_KEY => Some(kitty.name.to_vec()),
+			PARENTS_KEY => Self::kitty_parents(item).map(|parents| parents.encode()),
+			_ => None,
+		}
+	}
+
+	fn can_transfer(_collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+		// 拍卖中的kitty不能转移
+		KittyOwner::<T>::contains_key(item) && !Auctions::<T>::contains_key(item)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		let owner = Self::kitty_owner(item).ok_or(Error::<T>::InvalidKittyId)?;
+		ensure!(!Auctions::<T>::contains_key(item), Error::<T>::InAuction);
+		// 和transfer一样，转移后原来的上架信息失效
+		if Self::do_transfer(*item, &owner, destination)? {
+			let event = Event::KittySaleCancelled { sender: owner.clone(), kitty_id: *item };
+			Self::deposit_event(event);
+		}
+
+		Self::deposit_event(Event::KittyTransfered {
+			sender: owner,
+			to: destination.clone(),
+			kitty_id: *item,
+		});
+		Ok(())
+	}
+}
+
+/// mint时的item配置即新kitty的名字
+impl<T: Config> Mutate<T::AccountId, [u8; 8]> for Pallet<T> {
+	fn mint_into(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		who: &T::AccountId,
+		name: &[u8; 8],
+		_deposit_collection_owner: bool,
+	) -> DispatchResult {
+		// kitty的id是顺序分配的，只能mint下一个id
+		ensure!(*item == Self::next_kitty_id(), Error::<T>::InvalidKittyId);
+		Self::do_create(who, *name)?;
+		Ok(())
+	}
+
+	fn burn(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		maybe_check_owner: Option<&T::AccountId>,
+	) -> DispatchResult {
+		let owner = Self::kitty_owner(item).ok_or(Error::<T>::InvalidKittyId)?;
+		if let Some(check_owner) = maybe_check_owner {
+			ensure!(*check_owner == owner, Error::<T>::NotOwner);
+		}
+		Self::do_release(&owner, *item)
+	}
+}
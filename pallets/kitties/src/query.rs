@@ -0,0 +1,90 @@
+//! 给runtime api使用的查询方法，避免前端直接解码存储
+use crate::*;
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+/// 家族树最多查询的代数
+pub const MAX_FAMILY_TREE_DEPTH: u32 = 16;
+
+/// kitty的完整信息
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct KittyDetails<AccountId, Balance, BlockNumber> {
+	pub kitty_id: KittyId,
+	pub owner: AccountId,
+	pub kitty: Kitty<BlockNumber>,
+	pub parents: Option<(KittyId, KittyId)>,
+	pub listing: Option<Listing<Balance, BlockNumber>>,
+}
+
+pub type KittyDetailsOf<T> =
+	KittyDetails<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+/// 家族树中的一个kitty，depth为0的是查询的kitty本身，1是父母，2是祖父母，依此类推
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct FamilyMember {
+	pub kitty_id: KittyId,
+	pub depth: u32,
+	pub parents: Option<(KittyId, KittyId)>,
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn kitty_details(kitty_id: KittyId) -> Option<KittyDetailsOf<T>> {
+		Some(KittyDetails {
+			kitty_id,
+			owner: Self::kitty_owner(kitty_id)?,
+			kitty: Self::kitties(kitty_id)?,
+			parents: Self::kitty_parents(kitty_id),
+			listing: Self::kitties_on_sale(kitty_id),
+		})
+	}
+
+	pub fn kitties_of(owner: &T::AccountId) -> Vec<KittyDetailsOf<T>> {
+		Self::owned_kitties(owner).into_iter().filter_map(Self::kitty_details).collect()
+	}
+
+	/// 所有在售的kitty，包括卖家和价格，已经过期的上架不返回
+	pub fn listings() -> Vec<(KittyId, T::AccountId, ListingOf<T>)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		KittiesOnSale::<T>::iter()
+			.filter(|(_, listing)| listing.expires_at.map_or(true, |expires_at| now < expires_at))
+			.filter_map(|(kitty_id, listing)| {
+				Self::kitty_owner(kitty_id).map(|seller| (kitty_id, seller, listing))
+			})
+			.collect()
+	}
+
+	/// 从kitty_id往上查询generations代祖先，按代数从近到远返回，近亲繁殖时重复的祖先只返回一次
+	pub fn family_tree(kitty_id: KittyId, generations: u32) -> Vec<FamilyMember> {
+		let mut tree = Vec::new();
+		if !Kitties::<T>::contains_key(kitty_id) {
+			return tree
+		}
+
+		let generations = generations.min(MAX_FAMILY_TREE_DEPTH);
+		let mut visited = BTreeSet::new();
+		let mut current = Vec::from([kitty_id]);
+		visited.insert(kitty_id);
+		for depth in 0..=generations {
+			let mut next = Vec::new();
+			for id in current {
+				let parents = Self::kitty_parents(id);
+				if let Some((parent1, parent2)) = parents {
+					if depth < generations {
+						for parent in [parent1, parent2] {
+							if visited.insert(parent) {
+								next.push(parent);
+							}
+						}
+					}
+				}
+				tree.push(FamilyMember { kitty_id: id, depth, parents });
+			}
+			if next.is_empty() {
+				break
+			}
+			current = next;
+		}
+		tree
+	}
+}
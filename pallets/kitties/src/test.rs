@@ -1,5 +1,15 @@
-use crate::{mock::*, Event, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{genetics, mock::*, AuctionKind, Event, Error, FamilyMember, Listing};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::Permill;
+
+fn run_to_block(n: u64) {
+  while System::block_number() < n {
+    let next = System::block_number() + 1;
+    System::set_block_number(next);
+    KittiesModule::on_initialize(next);
+  }
+}
 
 #[test]
 fn create_kities_works() {
@@ -25,6 +35,9 @@ fn create_kities_works() {
 		assert_eq!(KittiesModule::kitties(kitty_id).is_some(), true); // 创建的kitty是存在的
 		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id)); // kitty的owner
 		assert_eq!(KittiesModule::kitty_parents(kitty_id), None); // kitty的parent不存在
+		// 创建kitty时质押了KittyPrice
+		assert_eq!(KittiesModule::kitty_deposit(kitty_id), KittyPrice::get());
+		assert_eq!(Balances::reserved_balance(account_id), KittyPrice::get());
 
 		crate::NextKittyId::<Test>::set(crate::KittyId::max_value());
 		assert_noop!(
@@ -80,6 +93,18 @@ fn breed_kitties_works() {
     assert_eq!(KittiesModule::kitty_owner(breed_kitty_id), Some(account_id)); // breed产生的kitty的owner是accout_id
 
     assert_eq!(KittiesModule::kitty_parents(breed_kitty_id), Some((kitty_id, kitty_id + 1))); // 验证parent
+
+    // 子代的代数和出生区块
+    let child = KittiesModule::kitties(breed_kitty_id).unwrap();
+    assert_eq!(child.generation, 1);
+    assert_eq!(child.birth_block, 1);
+    assert_eq!(child.last_bred, None);
+
+    // 父母的繁殖信息
+    let parent = KittiesModule::kitties(kitty_id).unwrap();
+    assert_eq!(parent.generation, 0);
+    assert_eq!(parent.last_bred, Some(1));
+    assert_eq!(parent.offspring, 1);
 	});
 }
 
@@ -96,6 +121,12 @@ fn transfer_kitties_works() {
       account_id,
       1_000_000_000,
     );
+    // 接收方需要承接kitty的质押
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      receiver_id,
+      1_000_000_000,
+    );
 
     assert_eq!(KittiesModule::next_kitty_id(), kitty_id);
 
@@ -121,6 +152,10 @@ fn transfer_kitties_works() {
     // kitty的owner
     assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(receiver_id));
 
+    // 质押随kitty转移
+    assert_eq!(Balances::reserved_balance(account_id), 0);
+    assert_eq!(Balances::reserved_balance(receiver_id), KittyPrice::get());
+
     System::assert_has_event(
       Event::KittyTransfered {
       sender: account_id,
@@ -146,7 +181,7 @@ fn sale_kitties_works() {
     assert_eq!(KittiesModule::next_kitty_id(), kitty_id);
     // kitty是否存在
     assert_noop!(
-      KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id),
+      KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 1_000, None),
       Error::<Test>::KittyNotExist
     );
 
@@ -155,16 +190,28 @@ fn sale_kitties_works() {
 
     // 发起方不是kitty的owner
     assert_noop!(
-      KittiesModule::sale(RuntimeOrigin::signed(account_id + 1), kitty_id),
+      KittiesModule::sale(RuntimeOrigin::signed(account_id + 1), kitty_id, 1_000, None),
       Error::<Test>::NotOwner
     );
 
+    // 过期区块不能早于当前区块
+    assert_noop!(
+      KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 1_000, Some(1)),
+      Error::<Test>::InvalidExpiry
+    );
+
     // 开始上架
-    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id));
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 1_000, Some(10)));
+
+    // 上架信息
+    assert_eq!(
+      KittiesModule::kitties_on_sale(kitty_id),
+      Some(Listing { price: 1_000, expires_at: Some(10) })
+    );
 
     // 再次上架，已经onSale
     assert_noop!(
-      KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id),
+      KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 2_000, None),
       Error::<Test>::AlreadyOnSale
     );
 
@@ -173,6 +220,8 @@ fn sale_kitties_works() {
       Event::KittyOnSale {
       sender: account_id,
       kitty_id,
+      price: 1_000,
+      expires_at: Some(10),
     }.into());
 
   })
@@ -201,15 +250,36 @@ fn buy_kitties_works() {
 
     // 存在这个kitty
     assert_noop!(
-      KittiesModule::buy(RuntimeOrigin::signed(owner_id), kitty_id+1),
+      KittiesModule::buy(RuntimeOrigin::signed(owner_id), kitty_id+1, 1_000),
       Error::<Test>::InvalidKittyId
     );
+
+    // 没有上架
+    assert_noop!(
+      KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 1_000),
+      Error::<Test>::NotOnSale
+    );
     
     // 上架
-    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id));
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id, 1_000, None));
+
+    // owner不能购买自己的kitty
+    assert_noop!(
+      KittiesModule::buy(RuntimeOrigin::signed(owner_id), kitty_id, 1_000),
+      Error::<Test>::AlreadyOwned
+    );
+
+    // 价格超过买家的上限
+    assert_noop!(
+      KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 999),
+      Error::<Test>::PriceTooHigh
+    );
+
+    let owner_balance = Balances::free_balance(owner_id);
+    let buyer_balance = Balances::free_balance(buyer_id);
 
     // 购买
-    assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id));
+    assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 1_000));
 
     // 验证链上数据
     
@@ -218,5 +288,1647 @@ fn buy_kitties_works() {
 
     // kitty的owner改变
     assert_eq!(KittiesModule::kitty_owner(kitty_id).unwrap(), buyer_id);
+
+    // 按照卖家设定的价格付款
+    assert_eq!(Balances::free_balance(owner_id), owner_balance + 1_000);
+    assert_eq!(Balances::free_balance(buyer_id), buyer_balance - 1_000);
+
+    System::assert_last_event(
+      Event::KittyBought {
+      buyer: buyer_id,
+      seller: owner_id,
+      kitty_id,
+      price: 1_000,
+    }.into());
+  })
+}
+
+#[test]
+fn buy_expired_listing_fails() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let buyer_id = 2;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      buyer_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id, 1_000, Some(5)));
+
+    // 到达过期区块后不能再购买
+    System::set_block_number(5);
+    assert_noop!(
+      KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 1_000),
+      Error::<Test>::ListingExpired
+    );
+  })
+} 
+#[test]
+fn cancel_sale_works() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let buyer_id = 2;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      buyer_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+
+    // 没有上架不能下架
+    assert_noop!(
+      KittiesModule::cancel_sale(RuntimeOrigin::signed(owner_id), kitty_id),
+      Error::<Test>::NotOnSale
+    );
+
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id, 1_000, None));
+
+    // 只有owner可以下架
+    assert_noop!(
+      KittiesModule::cancel_sale(RuntimeOrigin::signed(buyer_id), kitty_id),
+      Error::<Test>::NotOwner
+    );
+
+    assert_ok!(KittiesModule::cancel_sale(RuntimeOrigin::signed(owner_id), kitty_id));
+    assert_eq!(KittiesModule::kitties_on_sale(kitty_id), None);
+    System::assert_last_event(
+      Event::KittySaleCancelled {
+      sender: owner_id,
+      kitty_id,
+    }.into());
+
+    // 下架后不能再购买
+    assert_noop!(
+      KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 1_000),
+      Error::<Test>::NotOnSale
+    );
+  })
+}
+
+#[test]
+fn update_listing_works() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let buyer_id = 2;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      buyer_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+
+    // 没有上架不能修改
+    assert_noop!(
+      KittiesModule::update_listing(RuntimeOrigin::signed(owner_id), kitty_id, 2_000, None),
+      Error::<Test>::NotOnSale
+    );
+
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id, 1_000, None));
+
+    // 只有owner可以修改
+    assert_noop!(
+      KittiesModule::update_listing(RuntimeOrigin::signed(buyer_id), kitty_id, 2_000, None),
+      Error::<Test>::NotOwner
+    );
+    // 过期区块不能早于当前区块
+    assert_noop!(
+      KittiesModule::update_listing(RuntimeOrigin::signed(owner_id), kitty_id, 2_000, Some(1)),
+      Error::<Test>::InvalidExpiry
+    );
+
+    assert_ok!(KittiesModule::update_listing(RuntimeOrigin::signed(owner_id), kitty_id, 2_000, Some(20)));
+    assert_eq!(
+      KittiesModule::kitties_on_sale(kitty_id),
+      Some(Listing { price: 2_000, expires_at: Some(20) })
+    );
+    System::assert_last_event(
+      Event::KittyListingUpdated {
+      sender: owner_id,
+      kitty_id,
+      price: 2_000,
+      expires_at: Some(20),
+    }.into());
+
+    // 按旧价格购买会失败
+    assert_noop!(
+      KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 1_000),
+      Error::<Test>::PriceTooHigh
+    );
+    assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 2_000));
+  })
+}
+
+#[test]
+fn transfer_to_self_or_dead_account() {
+  new_test_ext().execute_with(|| {
+    let owner = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner,
+      1_000_000_000,
+    );
+    // MaxKittiesOwned = 5，拥有数量已经达到上限
+    for _ in 0..5 {
+      assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), *b"abcdefgg"));
+    }
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner), 0, 1_000, None));
+
+    // 转给自己不受上限限制，也不会下架
+    assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner), owner, 0));
+    assert_eq!(KittiesModule::kitty_owner(0), Some(owner));
+    assert_eq!(KittiesModule::owned_kitties(owner).len(), 5);
+    assert!(KittiesModule::kitties_on_sale(0).is_some());
+    assert_eq!(Balances::reserved_balance(owner), KittyPrice::get() * 5);
+
+    // 接收方账户不存在，无法承接质押
+    assert_noop!(
+      KittiesModule::transfer(RuntimeOrigin::signed(owner), 42, 1),
+      Error::<Test>::DeadBeneficiary
+    );
+  })
+}
+
+#[test]
+fn transfer_removes_listing() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let receiver_id = 2;
+    let buyer_id = 3;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      receiver_id,
+      1_000_000_000,
+    );
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      buyer_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id, 1_000, None));
+
+    // 转移后自动下架
+    assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner_id), receiver_id, kitty_id));
+    assert_eq!(KittiesModule::kitties_on_sale(kitty_id), None);
+    System::assert_has_event(
+      Event::KittySaleCancelled {
+      sender: owner_id,
+      kitty_id,
+    }.into());
+
+    // 新owner的kitty不能被购买
+    assert_noop!(
+      KittiesModule::buy(RuntimeOrigin::signed(buyer_id), kitty_id, 1_000),
+      Error::<Test>::NotOnSale
+    );
+  })
+}
+
+#[test]
+fn english_auction_works() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let bidder1 = 2;
+    let bidder2 = 3;
+    for account_id in [owner_id, bidder1, bidder2] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+
+    // 拍卖时长不合法
+    assert_noop!(
+      KittiesModule::create_auction(RuntimeOrigin::signed(owner_id), kitty_id, AuctionKind::English { min_bid: 1_000 }, 0),
+      Error::<Test>::InvalidAuctionDuration
+    );
+    assert_noop!(
+      KittiesModule::create_auction(RuntimeOrigin::signed(owner_id), kitty_id, AuctionKind::English { min_bid: 1_000 }, 101),
+      Error::<Test>::InvalidAuctionDuration
+    );
+
+    assert_ok!(KittiesModule::create_auction(
+      RuntimeOrigin::signed(owner_id),
+      kitty_id,
+      AuctionKind::English { min_bid: 1_000 },
+      10
+    ));
+    System::assert_last_event(
+      Event::AuctionCreated {
+      seller: owner_id,
+      kitty_id,
+      kind: AuctionKind::English { min_bid: 1_000 },
+      end: 11,
+    }.into());
+
+    // 拍卖中的kitty不能转移和上架
+    assert_noop!(
+      KittiesModule::transfer(RuntimeOrigin::signed(owner_id), bidder1, kitty_id),
+      Error::<Test>::InAuction
+    );
+    assert_noop!(
+      KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id, 1_000, None),
+      Error::<Test>::InAuction
+    );
+
+    // 低于起拍价
+    assert_noop!(
+      KittiesModule::bid(RuntimeOrigin::signed(bidder1), kitty_id, 999),
+      Error::<Test>::BidTooLow
+    );
+    // 卖家不能出价
+    assert_noop!(
+      KittiesModule::bid(RuntimeOrigin::signed(owner_id), kitty_id, 1_000),
+      Error::<Test>::AlreadyOwned
+    );
+
+    assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder1), kitty_id, 1_000));
+    assert_eq!(Balances::reserved_balance(bidder1), 1_000);
+
+    // 出价必须高于当前最高价
+    assert_noop!(
+      KittiesModule::bid(RuntimeOrigin::signed(bidder2), kitty_id, 1_000),
+      Error::<Test>::BidTooLow
+    );
+    assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder2), kitty_id, 1_500));
+
+    // 被超过的出价已经退回
+    assert_eq!(Balances::reserved_balance(bidder1), 0);
+    assert_eq!(Balances::reserved_balance(bidder2), 1_500);
+
+    // 有出价后不能取消
+    assert_noop!(
+      KittiesModule::cancel_auction(RuntimeOrigin::signed(owner_id), kitty_id),
+      Error::<Test>::AuctionHasBids
+    );
+
+    let owner_balance = Balances::free_balance(owner_id);
+    let bidder2_balance = Balances::free_balance(bidder2);
+
+    run_to_block(10);
+    assert!(KittiesModule::auctions(kitty_id).is_some());
+
+    // 到达结束区块自动结算
+    run_to_block(11);
+    assert!(KittiesModule::auctions(kitty_id).is_none());
+    assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(bidder2));
+    // 出价已经解除reserve，kitty的质押转移到了买家
+    assert_eq!(Balances::reserved_balance(bidder2), KittyPrice::get());
+    assert_eq!(Balances::reserved_balance(owner_id), 0);
+    assert_eq!(Balances::free_balance(bidder2), bidder2_balance);
+    assert_eq!(Balances::free_balance(owner_id), owner_balance + 1_500);
+    System::assert_has_event(
+      Event::KittyBought {
+      buyer: bidder2,
+      seller: owner_id,
+      kitty_id,
+      price: 1_500,
+    }.into());
+    System::assert_last_event(
+      Event::AuctionSettled {
+      kitty_id,
+      seller: owner_id,
+      buyer: bidder2,
+      price: 1_500,
+    }.into());
+
+    // 结束之后不能再出价
+    assert_noop!(
+      KittiesModule::bid(RuntimeOrigin::signed(bidder1), kitty_id, 2_000),
+      Error::<Test>::AuctionNotExist
+    );
+  })
+}
+
+#[test]
+fn english_auction_without_bids_ends() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create_auction(
+      RuntimeOrigin::signed(owner_id),
+      kitty_id,
+      AuctionKind::English { min_bid: 1_000 },
+      5
+    ));
+
+    run_to_block(6);
+    assert!(KittiesModule::auctions(kitty_id).is_none());
+    assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(owner_id));
+    System::assert_last_event(Event::AuctionEnded { kitty_id }.into());
+  })
+}
+
+#[test]
+fn bid_requires_room_for_kitty() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let bidder_id = 2;
+    for account_id in [owner_id, bidder_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create_auction(
+      RuntimeOrigin::signed(owner_id),
+      kitty_id,
+      AuctionKind::English { min_bid: 1_000 },
+      5
+    ));
+    // 出价者已经拥有MaxKittiesOwned个kitty
+    for _ in 0..5 {
+      assert_ok!(KittiesModule::create(RuntimeOrigin::signed(bidder_id), *b"abcdefgg"));
+    }
+    assert_noop!(
+      KittiesModule::bid(RuntimeOrigin::signed(bidder_id), kitty_id, 1_000),
+      Error::<Test>::TooManyKittiesOwned
+    );
+
+    assert_ok!(KittiesModule::release(RuntimeOrigin::signed(bidder_id), 1));
+    assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder_id), kitty_id, 1_000));
+    run_to_block(6);
+    assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(bidder_id));
   })
-} 
\ No newline at end of file
+}
+
+#[test]
+fn dutch_auction_works() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let buyer_id = 2;
+    for account_id in [owner_id, buyer_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+
+    // 起始价不能低于结束价
+    assert_noop!(
+      KittiesModule::create_auction(
+        RuntimeOrigin::signed(owner_id),
+        kitty_id,
+        AuctionKind::Dutch { start_price: 1_000, end_price: 2_000 },
+        10
+      ),
+      Error::<Test>::InvalidAuctionPrice
+    );
+
+    assert_ok!(KittiesModule::create_auction(
+      RuntimeOrigin::signed(owner_id),
+      kitty_id,
+      AuctionKind::Dutch { start_price: 2_000, end_price: 1_000 },
+      10
+    ));
+
+    // 过了一半时间，价格降到一半
+    run_to_block(6);
+    let auction = KittiesModule::auctions(kitty_id).unwrap();
+    assert_eq!(KittiesModule::dutch_price(&auction, 6), Some(1_500));
+
+    // 愿意支付的价格低于当前价格
+    assert_noop!(
+      KittiesModule::bid(RuntimeOrigin::signed(buyer_id), kitty_id, 1_499),
+      Error::<Test>::PriceTooHigh
+    );
+
+    let owner_balance = Balances::free_balance(owner_id);
+    // 以当前价格成交
+    assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(buyer_id), kitty_id, 2_000));
+    assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(buyer_id));
+    assert_eq!(Balances::free_balance(owner_id), owner_balance + 1_500);
+    assert!(KittiesModule::auctions(kitty_id).is_none());
+    assert!(crate::AuctionsEndingAt::<Test>::get(11).is_empty());
+  })
+}
+
+#[test]
+fn cancel_auction_works() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create_auction(
+      RuntimeOrigin::signed(owner_id),
+      kitty_id,
+      AuctionKind::English { min_bid: 1_000 },
+      5
+    ));
+
+    assert_noop!(
+      KittiesModule::cancel_auction(RuntimeOrigin::signed(owner_id + 1), kitty_id),
+      Error::<Test>::NotOwner
+    );
+    assert_ok!(KittiesModule::cancel_auction(RuntimeOrigin::signed(owner_id), kitty_id));
+    assert!(KittiesModule::auctions(kitty_id).is_none());
+    assert!(crate::AuctionsEndingAt::<Test>::get(6).is_empty());
+    System::assert_last_event(Event::AuctionCancelled { kitty_id }.into());
+
+    // 取消后可以正常转移
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id + 1,
+      1_000_000_000,
+    );
+    assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner_id), owner_id + 1, kitty_id));
+  })
+}
+
+#[test]
+fn breed_reserves_and_records_deposit() {
+  new_test_ext().execute_with(|| {
+    let owner_id = 1;
+    let receiver_id = 2;
+    for account_id in [owner_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(owner_id), 0, 1, *b"abcdefgg"));
+    assert_eq!(KittiesModule::kitty_deposit(2), KittyPrice::get());
+    assert_eq!(Balances::reserved_balance(owner_id), KittyPrice::get() * 3);
+
+    // 繁殖出来的kitty的质押随kitty转移，release时退回
+    assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner_id), receiver_id, 2));
+    assert_eq!(Balances::reserved_balance(owner_id), KittyPrice::get() * 2);
+    assert_eq!(Balances::reserved_balance(receiver_id), KittyPrice::get());
+    assert_ok!(KittiesModule::release(RuntimeOrigin::signed(receiver_id), 2));
+    assert_eq!(Balances::reserved_balance(receiver_id), 0);
+    assert_eq!(KittiesModule::kitty_deposit(2), 0);
+  })
+}
+
+#[test]
+fn release_kitty_works() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(owner_id), kitty_id, kitty_id + 1, *b"abcdefgg"));
+    let child_id = kitty_id + 2;
+    assert_eq!(Balances::reserved_balance(owner_id), KittyPrice::get() * 3);
+
+    // kitty不存在
+    assert_noop!(
+      KittiesModule::release(RuntimeOrigin::signed(owner_id), child_id + 1),
+      Error::<Test>::InvalidKittyId
+    );
+    // 只有owner可以释放
+    assert_noop!(
+      KittiesModule::release(RuntimeOrigin::signed(owner_id + 1), child_id),
+      Error::<Test>::NotOwner
+    );
+
+    let free_balance = Balances::free_balance(owner_id);
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), child_id, 1_000, None));
+    assert_ok!(KittiesModule::release(RuntimeOrigin::signed(owner_id), child_id));
+
+    // 质押已经退回
+    assert_eq!(Balances::reserved_balance(owner_id), KittyPrice::get() * 2);
+    assert_eq!(Balances::free_balance(owner_id), free_balance + KittyPrice::get());
+
+    // kitty的数据都被删除
+    assert_eq!(KittiesModule::kitties(child_id), None);
+    assert_eq!(KittiesModule::kitty_owner(child_id), None);
+    assert_eq!(KittiesModule::kitty_parents(child_id), None);
+    assert_eq!(KittiesModule::kitties_on_sale(child_id), None);
+    assert_eq!(KittiesModule::kitty_deposit(child_id), 0);
+
+    System::assert_last_event(
+      Event::KittyReleased {
+      sender: owner_id,
+      kitty_id: child_id,
+      deposit: KittyPrice::get(),
+    }.into());
+  })
+}
+
+#[test]
+fn release_kitty_in_auction_fails() {
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      owner_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create_auction(
+      RuntimeOrigin::signed(owner_id),
+      kitty_id,
+      AuctionKind::English { min_bid: 1_000 },
+      5
+    ));
+
+    assert_noop!(
+      KittiesModule::release(RuntimeOrigin::signed(owner_id), kitty_id),
+      Error::<Test>::InAuction
+    );
+  })
+}
+
+#[test]
+fn owned_kitties_index_works() {
+  new_test_ext().execute_with(|| {
+    let owner_id = 1;
+    let receiver_id = 2;
+    let buyer_id = 3;
+    for account_id in [owner_id, receiver_id, buyer_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    // create和breed
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(owner_id), 0, 1, *b"abcdefgg"));
+    assert_eq!(KittiesModule::owned_kitties(owner_id).into_inner(), vec![0, 1, 2]);
+
+    // transfer
+    assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner_id), receiver_id, 1));
+    assert_eq!(KittiesModule::owned_kitties(owner_id).into_inner(), vec![0, 2]);
+    assert_eq!(KittiesModule::owned_kitties(receiver_id).into_inner(), vec![1]);
+
+    // buy
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), 2, 1_000, None));
+    assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer_id), 2, 1_000));
+    assert_eq!(KittiesModule::owned_kitties(owner_id).into_inner(), vec![0]);
+    assert_eq!(KittiesModule::owned_kitties(buyer_id).into_inner(), vec![2]);
+
+    // release
+    assert_ok!(KittiesModule::release(RuntimeOrigin::signed(owner_id), 0));
+    assert!(KittiesModule::owned_kitties(owner_id).is_empty());
+  })
+}
+
+#[test]
+fn max_kitties_owned_works() {
+  new_test_ext().execute_with(|| {
+    let owner_id = 1;
+    let receiver_id = 2;
+    for account_id in [owner_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    // MaxKittiesOwned = 5
+    for _ in 0..5 {
+      assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    }
+    assert_noop!(
+      KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"),
+      Error::<Test>::TooManyKittiesOwned
+    );
+    assert_noop!(
+      KittiesModule::breed(RuntimeOrigin::signed(owner_id), 0, 1, *b"abcdefgg"),
+      Error::<Test>::TooManyKittiesOwned
+    );
+
+    // 接收方拥有的kitty已达上限
+    for _ in 0..5 {
+      assert_ok!(KittiesModule::create(RuntimeOrigin::signed(receiver_id), *b"abcdefgg"));
+    }
+    assert_noop!(
+      KittiesModule::transfer(RuntimeOrigin::signed(owner_id), receiver_id, 0),
+      Error::<Test>::TooManyKittiesOwned
+    );
+  })
+}
+
+#[test]
+fn migrate_v3_builds_owned_kitties_index() {
+  use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(2).put::<KittiesModule>();
+    crate::KittyOwner::<Test>::insert(0, 1);
+    crate::KittyOwner::<Test>::insert(1, 2);
+    crate::KittyOwner::<Test>::insert(2, 1);
+
+    crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+    let mut owned = KittiesModule::owned_kitties(1).into_inner();
+    owned.sort();
+    assert_eq!(owned, vec![0, 2]);
+    assert_eq!(KittiesModule::owned_kitties(2).into_inner(), vec![1]);
+    assert_eq!(KittiesModule::on_chain_storage_version(), 3);
+  })
+}
+
+#[test]
+fn migrate_v3_skipped_when_owner_over_cap() {
+  use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(2).put::<KittiesModule>();
+    // MaxKittiesOwned是5
+    for kitty_id in 0..6 {
+      crate::KittyOwner::<Test>::insert(kitty_id, 1);
+    }
+    crate::KittyOwner::<Test>::insert(6, 2);
+
+    // 不会只建立一部分索引，版本保持2
+    crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+    assert!(KittiesModule::owned_kitties(1).is_empty());
+    assert!(KittiesModule::owned_kitties(2).is_empty());
+    assert_eq!(KittiesModule::on_chain_storage_version(), 2);
+
+    // 调整之后再升级
+    crate::KittyOwner::<Test>::remove(5);
+    crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+    assert_eq!(KittiesModule::owned_kitties(1).len(), 5);
+    assert_eq!(KittiesModule::owned_kitties(2).into_inner(), vec![6]);
+    assert_eq!(KittiesModule::on_chain_storage_version(), 3);
+  })
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migrate_v3_rejects_owner_over_cap() {
+  use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(2).put::<KittiesModule>();
+    // MaxKittiesOwned是5
+    for kitty_id in 0..6 {
+      crate::KittyOwner::<Test>::insert(kitty_id, 1);
+    }
+    assert!(crate::migrations::v3::MigrateToV3::<Test>::pre_upgrade().is_err());
+
+    crate::KittyOwner::<Test>::remove(5);
+    assert!(crate::migrations::v3::MigrateToV3::<Test>::pre_upgrade().is_ok());
+  })
+}
+
+// 用计数器的哈希生成确定的"随机"输入，对大量样本检查基因遗传的性质
+fn sample_dna(n: u32, tag: u8) -> [u8; 16] {
+  sp_io::hashing::blake2_128(&(n, tag).encode())
+}
+
+fn sample_seed(n: u32) -> [u8; 32] {
+  sp_io::hashing::blake2_256(&(n, b"seed").encode())
+}
+
+#[test]
+fn child_only_inherits_parent_alleles_without_mutation() {
+  for n in 0..1_000 {
+    let parent1 = sample_dna(n, 1);
+    let parent2 = sample_dna(n, 2);
+    let (child, mutations) =
+      genetics::inherit_traced(&parent1, &parent2, &sample_seed(n), Permill::zero());
+
+    assert_eq!(mutations, 0);
+    for locus in genetics::Locus::ALL {
+      let (from1, from2) = genetics::alleles(&child, locus);
+      let (a1, b1) = genetics::alleles(&parent1, locus);
+      let (a2, b2) = genetics::alleles(&parent2, locus);
+      assert!(from1 == a1 || from1 == b1);
+      assert!(from2 == a2 || from2 == b2);
+    }
+  }
+}
+
+#[test]
+fn child_only_inherits_parent_alleles_except_mutations() {
+  for n in 0..1_000 {
+    let parent1 = sample_dna(n, 1);
+    let parent2 = sample_dna(n, 2);
+    let (child, mutations) =
+      genetics::inherit_traced(&parent1, &parent2, &sample_seed(n), Permill::from_percent(20));
+
+    for locus in genetics::Locus::ALL {
+      let index = locus as usize * 2;
+      let (a1, b1) = genetics::alleles(&parent1, locus);
+      let (a2, b2) = genetics::alleles(&parent2, locus);
+      if mutations & (1 << index) == 0 {
+        assert!(child[index] == a1 || child[index] == b1);
+      }
+      if mutations & (1 << (index + 1)) == 0 {
+        assert!(child[index + 1] == a2 || child[index + 1] == b2);
+      }
+    }
+  }
+}
+
+#[test]
+fn inheritance_is_unbiased_between_alleles() {
+  // 两个父母的每个等位基因都有机会被继承，孩子不会偏向0
+  let parent1 = [0xffu8, 0x00].repeat(8).try_into().unwrap();
+  let parent2 = [0x0fu8, 0xf0].repeat(8).try_into().unwrap();
+  let mut seen = [[false; 2]; 2];
+  for n in 0..200 {
+    let child = genetics::inherit(&parent1, &parent2, &sample_seed(n), Permill::zero());
+    let (from1, from2) = genetics::alleles(&child, genetics::Locus::Fur);
+    seen[0][(from1 == 0x00) as usize] = true;
+    seen[1][(from2 == 0xf0) as usize] = true;
+  }
+  assert_eq!(seen, [[true; 2]; 2]);
+}
+
+#[test]
+fn mutation_rate_is_respected() {
+  let mut mutated = 0u32;
+  for n in 0..1_000 {
+    let (_, mutations) = genetics::inherit_traced(
+      &sample_dna(n, 1),
+      &sample_dna(n, 2),
+      &sample_seed(n),
+      Permill::from_percent(10),
+    );
+    mutated += mutations.count_ones();
+  }
+  // 16_000个等位基因，期望突变1_600个
+  assert!(mutated > 1_300 && mutated < 1_900);
+
+  let (_, mutations) =
+    genetics::inherit_traced(&sample_dna(0, 1), &sample_dna(0, 2), &sample_seed(0), Permill::one());
+  assert_eq!(mutations, u16::MAX);
+}
+
+#[test]
+fn dominant_allele_is_expressed() {
+  let mut dna = [0u8; 16];
+  // 显性 + 隐性: 表现显性
+  dna[0] = 0x81;
+  dna[1] = 0x7f;
+  assert_eq!(genetics::expressed(&dna, genetics::Locus::Fur), 0x81);
+  // 隐性 + 显性
+  dna[2] = 0x10;
+  dna[3] = 0x90;
+  assert_eq!(genetics::expressed(&dna, genetics::Locus::Pattern), 0x90);
+  // 都是隐性
+  dna[4] = 0x10;
+  dna[5] = 0x20;
+  assert_eq!(genetics::expressed(&dna, genetics::Locus::EyeColor), 0x20);
+}
+
+#[test]
+fn breed_uses_parent_genes() {
+  new_test_ext().execute_with(|| {
+    let account_id = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      account_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"));
+
+    let parent1 = KittiesModule::kitties(0).unwrap().dna;
+    let parent2 = KittiesModule::kitties(1).unwrap().dna;
+    let child = KittiesModule::kitties(2).unwrap().dna;
+
+    // MutationRate为1%，绝大多数等位基因来自父母
+    let inherited = genetics::Locus::ALL
+      .iter()
+      .map(|locus| {
+        let (from1, from2) = genetics::alleles(&child, *locus);
+        let (a1, b1) = genetics::alleles(&parent1, *locus);
+        let (a2, b2) = genetics::alleles(&parent2, *locus);
+        (from1 == a1 || from1 == b1) as u32 + (from2 == a2 || from2 == b2) as u32
+      })
+      .sum::<u32>();
+    assert!(inherited >= 14);
+  })
+}
+
+#[test]
+fn breeding_cooldown_works() {
+  new_test_ext().execute_with(|| {
+    let account_id = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      account_id,
+      1_000_000_000,
+    );
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"));
+
+    // 第0代的冷却时间是BreedingCooldown
+    assert_eq!(KittiesModule::next_breedable_block(&KittiesModule::kitties(0).unwrap()), Some(11));
+    run_to_block(10);
+    assert_noop!(
+      KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"),
+      Error::<Test>::BreedingCooldown
+    );
+
+    run_to_block(11);
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"));
+
+    // 第1代的冷却时间是BreedingCooldown的两倍
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 2, 3, *b"abcdefgg"));
+    let child = KittiesModule::kitties(4).unwrap();
+    assert_eq!(child.generation, 2);
+    assert_eq!(KittiesModule::next_breedable_block(&KittiesModule::kitties(2).unwrap()), Some(31));
+  })
+}
+
+#[test]
+fn max_offspring_works() {
+  new_test_ext().execute_with(|| {
+    let account_id = 1;
+    let receiver_id = 2;
+    for account_id in [account_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+
+    // MaxOffspring = 3
+    for round in 0..3 {
+      run_to_block(1 + round * 10);
+      assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"));
+      let child_id = KittiesModule::next_kitty_id() - 1;
+      assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), receiver_id, child_id));
+    }
+    assert_eq!(KittiesModule::kitties(0).unwrap().offspring, 3);
+
+    run_to_block(100);
+    assert_noop!(
+      KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"),
+      Error::<Test>::TooManyOffspring
+    );
+  })
+}
+
+#[test]
+fn migrate_v4_adds_breeding_info() {
+  use crate::migrations::v2::v2_Kitty;
+  use frame_support::{
+    migration::put_storage_value,
+    storage::StoragePrefixedMap,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    Blake2_128Concat, StorageHasher,
+  };
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(3).put::<KittiesModule>();
+    let module = crate::Kitties::<Test>::module_prefix();
+    let item = crate::Kitties::<Test>::storage_prefix();
+    for kitty_id in 0u32..4 {
+      let kitty = v2_Kitty { dna: [kitty_id as u8; 16], name: *b"abcdefgh" };
+      put_storage_value(module, item, &Blake2_128Concat::hash(&kitty_id.encode()), kitty);
+    }
+    // 2是0和1的孩子，3是1和2的孩子
+    crate::KittyParents::<Test>::insert(2, (0, 1));
+    crate::KittyParents::<Test>::insert(3, (1, 2));
+
+    crate::migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+    let kitty = |kitty_id| KittiesModule::kitties(kitty_id).unwrap();
+    assert_eq!(kitty(0).dna, [0; 16]);
+    assert_eq!(kitty(0).name, *b"abcdefgh");
+    assert_eq!((kitty(0).generation, kitty(0).offspring), (0, 1));
+    assert_eq!((kitty(1).generation, kitty(1).offspring), (0, 2));
+    assert_eq!((kitty(2).generation, kitty(2).offspring), (1, 1));
+    assert_eq!((kitty(3).generation, kitty(3).offspring), (2, 0));
+    assert_eq!(kitty(3).birth_block, 1);
+    assert_eq!(KittiesModule::on_chain_storage_version(), 4);
+  })
+}
+
+#[test]
+fn migrate_v5_converts_listings() {
+  use frame_support::{
+    migration::put_storage_value,
+    storage::StoragePrefixedMap,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    Blake2_128Concat, StorageHasher,
+  };
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(4).put::<KittiesModule>();
+    let module = crate::KittiesOnSale::<Test>::module_prefix();
+    let item = crate::KittiesOnSale::<Test>::storage_prefix();
+    for kitty_id in [0u32, 2] {
+      put_storage_value(module, item, &Blake2_128Concat::hash(&kitty_id.encode()), ());
+    }
+
+    crate::migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+
+    // 旧的上架以KittyPrice出售
+    let listing = Listing { price: KittyPrice::get(), expires_at: None };
+    assert_eq!(KittiesModule::kitties_on_sale(0), Some(listing.clone()));
+    assert_eq!(KittiesModule::kitties_on_sale(1), None);
+    assert_eq!(KittiesModule::kitties_on_sale(2), Some(listing));
+    assert_eq!(KittiesModule::on_chain_storage_version(), 5);
+  })
+}
+
+#[test]
+fn migrate_v6_returns_deposits_to_owners() {
+  use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+  use sp_runtime::traits::AccountIdConversion;
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(5).put::<KittiesModule>();
+    // 旧版本把两个kitty的价格付给了pallet账户
+    let pallet_account: u64 = KittyPalletId::get().into_account_truncating();
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      pallet_account,
+      KittyPrice::get() * 2,
+    );
+    for (kitty_id, owner_id) in [(0, 1), (1, 2), (2, 2)] {
+      crate::KittyOwner::<Test>::insert(kitty_id, owner_id);
+    }
+    for owner_id in [1, 2] {
+      let _res = Balances::force_set_balance(RuntimeOrigin::root(), owner_id, 1_000);
+    }
+
+    crate::migrations::v6::MigrateToV6::<Test, KittyPalletId>::on_runtime_upgrade();
+
+    assert_eq!(KittiesModule::kitty_deposit(0), KittyPrice::get());
+    assert_eq!(KittiesModule::kitty_deposit(1), KittyPrice::get());
+    assert_eq!(Balances::reserved_balance(1), KittyPrice::get());
+    assert_eq!(Balances::free_balance(1), 1_000);
+    assert_eq!(Balances::reserved_balance(2), KittyPrice::get());
+    assert_eq!(Balances::free_balance(2), 1_000);
+    // pallet账户的余额不够第三个kitty的质押
+    assert!(crate::KittyDeposits::<Test>::contains_key(2));
+    assert_eq!(KittiesModule::kitty_deposit(2), 0);
+    assert_eq!(Balances::total_balance(&pallet_account), 0);
+    assert_eq!(KittiesModule::on_chain_storage_version(), 6);
+
+    // 迁移后的质押可以正常release
+    assert_ok!(KittiesModule::release(RuntimeOrigin::signed(1), 0));
+    assert_eq!(Balances::reserved_balance(1), 0);
+  })
+}
+
+#[test]
+fn breed_requires_owning_both_kitties() {
+  new_test_ext().execute_with(|| {
+    let account_id = 1;
+    let other_id = 2;
+    for account_id in [account_id, other_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(other_id), *b"abcdefgg"));
+
+    assert_noop!(
+      KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"),
+      Error::<Test>::NotOwner
+    );
+    assert_noop!(
+      KittiesModule::breed(RuntimeOrigin::signed(other_id), 0, 1, *b"abcdefgg"),
+      Error::<Test>::NotOwner
+    );
+  })
+}
+
+#[test]
+fn kitty_for_sale_cannot_breed() {
+  new_test_ext().execute_with(|| {
+    let owner = 1;
+    let renter = 2;
+    for account_id in [owner, renter] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(renter), *b"abcdefgg"));
+
+    // 出租之后上架
+    assert_ok!(KittiesModule::offer_sire(RuntimeOrigin::signed(owner), 0, 1_000));
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner), 0, 1_000, None));
+    assert_noop!(
+      KittiesModule::breed_with_sire(RuntimeOrigin::signed(renter), 2, 0, 1_000, *b"abcdefgg"),
+      Error::<Test>::AlreadyOnSale
+    );
+    assert_noop!(
+      KittiesModule::breed(RuntimeOrigin::signed(owner), 0, 1, *b"abcdefgg"),
+      Error::<Test>::AlreadyOnSale
+    );
+
+    // 正在拍卖
+    assert_ok!(KittiesModule::create_auction(
+      RuntimeOrigin::signed(owner),
+      1,
+      AuctionKind::English { min_bid: 1_000 },
+      5
+    ));
+    assert_noop!(
+      KittiesModule::offer_sire(RuntimeOrigin::signed(owner), 1, 1_000),
+      Error::<Test>::InAuction
+    );
+    assert_ok!(KittiesModule::cancel_sale(RuntimeOrigin::signed(owner), 0));
+    assert_noop!(
+      KittiesModule::breed(RuntimeOrigin::signed(owner), 0, 1, *b"abcdefgg"),
+      Error::<Test>::InAuction
+    );
+  })
+}
+
+#[test]
+fn sire_rental_works() {
+  new_test_ext().execute_with(|| {
+    let sire_owner = 1;
+    let renter = 2;
+    let sire_id = 0;
+    let matron_id = 1;
+    for account_id in [sire_owner, renter] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sire_owner), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(renter), *b"abcdefgg"));
+
+    // 没有出租
+    assert_noop!(
+      KittiesModule::breed_with_sire(RuntimeOrigin::signed(renter), matron_id, sire_id, 1_000, *b"abcdefgg"),
+      Error::<Test>::SireNotOffered
+    );
+
+    // 只有owner可以出租
+    assert_noop!(
+      KittiesModule::offer_sire(RuntimeOrigin::signed(renter), sire_id, 1_000),
+      Error::<Test>::NotOwner
+    );
+    assert_ok!(KittiesModule::offer_sire(RuntimeOrigin::signed(sire_owner), sire_id, 1_000));
+    System::assert_last_event(
+      Event::SireOffered {
+      owner: sire_owner,
+      kitty_id: sire_id,
+      fee: 1_000,
+    }.into());
+
+    // matron必须属于sender
+    assert_noop!(
+      KittiesModule::breed_with_sire(RuntimeOrigin::signed(sire_owner), matron_id, sire_id, 1_000, *b"abcdefgg"),
+      Error::<Test>::NotOwner
+    );
+    // 费用超过上限
+    assert_noop!(
+      KittiesModule::breed_with_sire(RuntimeOrigin::signed(renter), matron_id, sire_id, 999, *b"abcdefgg"),
+      Error::<Test>::PriceTooHigh
+    );
+
+    let sire_owner_balance = Balances::free_balance(sire_owner);
+    assert_ok!(KittiesModule::breed_with_sire(
+      RuntimeOrigin::signed(renter),
+      matron_id,
+      sire_id,
+      1_000,
+      *b"abcdefgg"
+    ));
+
+    // 新kitty属于renter，费用支付给sire的owner
+    let child_id = 2;
+    assert_eq!(KittiesModule::kitty_owner(child_id), Some(renter));
+    assert_eq!(KittiesModule::kitty_parents(child_id), Some((matron_id, sire_id)));
+    assert_eq!(Balances::free_balance(sire_owner), sire_owner_balance + 1_000);
+    assert_eq!(KittiesModule::kitties(sire_id).unwrap().offspring, 1);
+    System::assert_last_event(
+      Event::SireRented {
+      sire_id,
+      matron_id,
+      renter,
+      fee: 1_000,
+    }.into());
+
+    // 出租后sire还在冷却中
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(renter), *b"abcdefgg"));
+    assert_noop!(
+      KittiesModule::breed_with_sire(RuntimeOrigin::signed(renter), 3, sire_id, 1_000, *b"abcdefgg"),
+      Error::<Test>::BreedingCooldown
+    );
+  })
+}
+
+#[test]
+fn withdraw_sire_works() {
+  new_test_ext().execute_with(|| {
+    let owner_id = 1;
+    let receiver_id = 2;
+    let kitty_id = 0;
+    for account_id in [owner_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_noop!(
+      KittiesModule::withdraw_sire(RuntimeOrigin::signed(owner_id), kitty_id),
+      Error::<Test>::SireNotOffered
+    );
+
+    assert_ok!(KittiesModule::offer_sire(RuntimeOrigin::signed(owner_id), kitty_id, 1_000));
+    assert_ok!(KittiesModule::withdraw_sire(RuntimeOrigin::signed(owner_id), kitty_id));
+    assert_eq!(KittiesModule::sire_fee(kitty_id), None);
+    System::assert_last_event(Event::SireWithdrawn { owner: owner_id, kitty_id }.into());
+
+    // 转移kitty后出租自动取消
+    assert_ok!(KittiesModule::offer_sire(RuntimeOrigin::signed(owner_id), kitty_id, 1_000));
+    assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner_id), receiver_id, kitty_id));
+    assert_eq!(KittiesModule::sire_fee(kitty_id), None);
+  })
+}
+
+#[test]
+fn approve_and_transfer_from_works() {
+  new_test_ext().execute_with(|| {
+    let owner_id = 1;
+    let delegate_id = 2;
+    let receiver_id = 3;
+    let kitty_id = 0;
+    for account_id in [owner_id, delegate_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+
+    // 没有授权
+    assert_noop!(
+      KittiesModule::transfer_from(RuntimeOrigin::signed(delegate_id), owner_id, receiver_id, kitty_id),
+      Error::<Test>::NotApproved
+    );
+    // 只有owner或operator可以授权
+    assert_noop!(
+      KittiesModule::approve(RuntimeOrigin::signed(delegate_id), kitty_id, Some(delegate_id)),
+      Error::<Test>::NotApproved
+    );
+    assert_noop!(
+      KittiesModule::approve(RuntimeOrigin::signed(owner_id), kitty_id, Some(owner_id)),
+      Error::<Test>::ApproveToCaller
+    );
+
+    assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(owner_id), kitty_id, Some(delegate_id)));
+    assert_eq!(KittiesModule::approved(kitty_id), Some(delegate_id));
+    System::assert_last_event(
+      Event::Approval {
+      owner: owner_id,
+      approved: Some(delegate_id),
+      kitty_id,
+    }.into());
+
+    // from必须是owner
+    assert_noop!(
+      KittiesModule::transfer_from(RuntimeOrigin::signed(delegate_id), receiver_id, delegate_id, kitty_id),
+      Error::<Test>::NotOwner
+    );
+
+    assert_ok!(KittiesModule::transfer_from(RuntimeOrigin::signed(delegate_id), owner_id, receiver_id, kitty_id));
+    assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(receiver_id));
+    System::assert_last_event(
+      Event::KittyTransfered {
+      sender: owner_id,
+      to: receiver_id,
+      kitty_id,
+    }.into());
+
+    // owner变化后授权被清除
+    assert_eq!(KittiesModule::approved(kitty_id), None);
+    assert_noop!(
+      KittiesModule::transfer_from(RuntimeOrigin::signed(delegate_id), receiver_id, owner_id, kitty_id),
+      Error::<Test>::NotApproved
+    );
+  })
+}
+
+#[test]
+fn approval_for_all_works() {
+  new_test_ext().execute_with(|| {
+    let owner_id = 1;
+    let operator_id = 2;
+    let receiver_id = 3;
+    for account_id in [owner_id, operator_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+
+    assert_noop!(
+      KittiesModule::set_approval_for_all(RuntimeOrigin::signed(owner_id), owner_id, true),
+      Error::<Test>::ApproveToCaller
+    );
+    assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(owner_id), operator_id, true));
+    assert!(KittiesModule::is_approved_for_all(&owner_id, &operator_id));
+    System::assert_last_event(
+      Event::ApprovalForAll {
+      owner: owner_id,
+      operator: operator_id,
+      approved: true,
+    }.into());
+
+    // operator可以转移owner的所有kitty，也可以授权单个kitty
+    assert_ok!(KittiesModule::transfer_from(RuntimeOrigin::signed(operator_id), owner_id, receiver_id, 0));
+    assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(operator_id), 1, Some(receiver_id)));
+    System::assert_last_event(
+      Event::Approval {
+      owner: owner_id,
+      approved: Some(receiver_id),
+      kitty_id: 1,
+    }.into());
+
+    // 取消operator授权
+    assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(owner_id), operator_id, false));
+    assert!(!KittiesModule::is_approved_for_all(&owner_id, &operator_id));
+    assert_noop!(
+      KittiesModule::transfer_from(RuntimeOrigin::signed(operator_id), owner_id, receiver_id, 1),
+      Error::<Test>::NotApproved
+    );
+
+    // 单个kitty的授权还有效，可以取消
+    assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(owner_id), 1, None));
+    assert_eq!(KittiesModule::approved(1), None);
+  })
+}
+
+#[test]
+fn nonfungibles_traits_work() {
+  use crate::nonfungibles::{DNA_KEY, NAME_KEY, PARENTS_KEY};
+  use frame_support::traits::tokens::nonfungibles_v2::{Inspect, Mutate, Transfer};
+
+  type Nft = KittiesModule;
+
+  new_test_ext().execute_with(|| {
+    let owner_id = 1;
+    let receiver_id = 2;
+    for account_id in [owner_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    // 只能mint下一个id
+    assert_noop!(
+      <Nft as Mutate<u64, [u8; 8]>>::mint_into(&(), &1, &owner_id, b"abcdefgg", false),
+      Error::<Test>::InvalidKittyId
+    );
+    assert_ok!(<Nft as Mutate<u64, [u8; 8]>>::mint_into(&(), &0, &owner_id, b"abcdefgg", false));
+    assert_ok!(<Nft as Mutate<u64, [u8; 8]>>::mint_into(&(), &1, &owner_id, b"hijklmno", false));
+    assert_eq!(<Nft as Inspect<u64>>::owner(&(), &0), Some(owner_id));
+    assert_eq!(Balances::reserved_balance(owner_id), KittyPrice::get() * 2);
+
+    // 属性
+    let kitty = KittiesModule::kitties(0).unwrap();
+    assert_eq!(<Nft as Inspect<u64>>::attribute(&(), &0, DNA_KEY), Some(kitty.dna.to_vec()));
+    assert_eq!(<Nft as Inspect<u64>>::attribute(&(), &1, NAME_KEY), Some(b"hijklmno".to_vec()));
+    assert_eq!(<Nft as Inspect<u64>>::attribute(&(), &0, PARENTS_KEY), None);
+    assert_eq!(<Nft as Inspect<u64>>::attribute(&(), &0, b"unknown"), None);
+    assert_eq!(<Nft as Inspect<u64>>::attribute(&(), &9, DNA_KEY), None);
+
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(owner_id), 0, 1, *b"pqrstuvw"));
+    assert_eq!(<Nft as Inspect<u64>>::attribute(&(), &2, PARENTS_KEY), Some((0u32, 1u32).encode()));
+
+    // 转移
+    assert!(<Nft as Inspect<u64>>::can_transfer(&(), &0));
+    assert!(!<Nft as Inspect<u64>>::can_transfer(&(), &9));
+    assert_ok!(<Nft as Transfer<u64>>::transfer(&(), &0, &receiver_id));
+    assert_eq!(<Nft as Inspect<u64>>::owner(&(), &0), Some(receiver_id));
+    assert_eq!(Balances::reserved_balance(receiver_id), KittyPrice::get());
+    assert_noop!(
+      <Nft as Transfer<u64>>::transfer(&(), &9, &receiver_id),
+      Error::<Test>::InvalidKittyId
+    );
+
+    // burn
+    assert_noop!(
+      <Nft as Mutate<u64, [u8; 8]>>::burn(&(), &0, Some(&owner_id)),
+      Error::<Test>::NotOwner
+    );
+    assert_ok!(<Nft as Mutate<u64, [u8; 8]>>::burn(&(), &0, Some(&receiver_id)));
+    assert_eq!(<Nft as Inspect<u64>>::owner(&(), &0), None);
+    assert_eq!(Balances::reserved_balance(receiver_id), 0);
+    assert_ok!(<Nft as Mutate<u64, [u8; 8]>>::burn(&(), &1, None));
+    assert_eq!(<Nft as Inspect<u64>>::owner(&(), &1), None);
+  })
+}
+
+#[test]
+fn nonfungibles_transfer_cancels_sale() {
+  use frame_support::traits::tokens::nonfungibles_v2::Transfer;
+
+  new_test_ext().execute_with(|| {
+    let kitty_id = 0;
+    let owner_id = 1;
+    let receiver_id = 2;
+    for account_id in [owner_id, receiver_id] {
+      let _res = Balances::force_set_balance(
+        RuntimeOrigin::root(),
+        account_id,
+        1_000_000_000,
+      );
+    }
+
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner_id), kitty_id, 1_000, None));
+    assert_ok!(<KittiesModule as Transfer<u64>>::transfer(&(), &kitty_id, &receiver_id));
+
+    assert_eq!(KittiesModule::kitties_on_sale(kitty_id), None);
+    System::assert_has_event(Event::KittySaleCancelled { sender: owner_id, kitty_id }.into());
+    System::assert_last_event(
+      Event::KittyTransfered { sender: owner_id, to: receiver_id, kitty_id }.into(),
+    );
+  })
+}
+
+#[test]
+fn query_api_works() {
+  new_test_ext().execute_with(|| {
+    let account_id = 1;
+    let _res = Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      account_id,
+      1_000_000_000,
+    );
+
+    assert_eq!(KittiesModule::kitty_details(0), None);
+    assert_eq!(KittiesModule::family_tree(0, 3), vec![]);
+
+    // 0、1繁殖出2，2和3繁殖出4
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, *b"abcdefgg"));
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"abcdefgg"));
+    assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 2, 3, *b"abcdefgg"));
+
+    let details = KittiesModule::kitty_details(4).unwrap();
+    assert_eq!(details.owner, account_id);
+    assert_eq!(details.kitty, KittiesModule::kitties(4).unwrap());
+    assert_eq!(details.parents, Some((2, 3)));
+    assert_eq!(details.listing, None);
+    assert_eq!(
+      KittiesModule::kitties_of(&account_id).iter().map(|d| d.kitty_id).collect::<Vec<_>>(),
+      vec![0, 1, 2, 3, 4]
+    );
+
+    // 家族树
+    assert_eq!(
+      KittiesModule::family_tree(4, 1),
+      vec![
+        FamilyMember { kitty_id: 4, depth: 0, parents: Some((2, 3)) },
+        FamilyMember { kitty_id: 2, depth: 1, parents: Some((0, 1)) },
+        FamilyMember { kitty_id: 3, depth: 1, parents: None },
+      ]
+    );
+    assert_eq!(
+      KittiesModule::family_tree(4, 10),
+      vec![
+        FamilyMember { kitty_id: 4, depth: 0, parents: Some((2, 3)) },
+        FamilyMember { kitty_id: 2, depth: 1, parents: Some((0, 1)) },
+        FamilyMember { kitty_id: 3, depth: 1, parents: None },
+        FamilyMember { kitty_id: 0, depth: 2, parents: None },
+        FamilyMember { kitty_id: 1, depth: 2, parents: None },
+      ]
+    );
+    assert_eq!(
+      KittiesModule::family_tree(4, 0),
+      vec![FamilyMember { kitty_id: 4, depth: 0, parents: Some((2, 3)) }]
+    );
+
+    // 在售列表不包含过期的上架
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), 0, 1_000, None));
+    assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), 1, 2_000, Some(5)));
+    let mut listings = KittiesModule::listings();
+    listings.sort_by_key(|(kitty_id, _, _)| *kitty_id);
+    assert_eq!(
+      listings,
+      vec![
+        (0, account_id, Listing { price: 1_000, expires_at: None }),
+        (1, account_id, Listing { price: 2_000, expires_at: Some(5) }),
+      ]
+    );
+    assert_eq!(
+      KittiesModule::kitty_details(1).unwrap().listing,
+      Some(Listing { price: 2_000, expires_at: Some(5) })
+    );
+
+    System::set_block_number(6);
+    assert_eq!(
+      KittiesModule::listings(),
+      vec![(0, account_id, Listing { price: 1_000, expires_at: None })]
+    );
+  })
+}
+
+// 按旧版本的格式写入kitty
+fn put_old_kitty<V: Encode>(kitty_id: u32, kitty: V) {
+  use frame_support::{
+    migration::put_storage_value, storage::StoragePrefixedMap, Blake2_128Concat, StorageHasher,
+  };
+  put_storage_value(
+    crate::Kitties::<Test>::module_prefix(),
+    crate::Kitties::<Test>::storage_prefix(),
+    &Blake2_128Concat::hash(&kitty_id.encode()),
+    kitty,
+  );
+}
+
+// 依次执行所有迁移，打开try-runtime时同时执行pre_upgrade和post_upgrade检查
+fn run_migrations() {
+  use crate::migrations::{
+    v2::MigrateToV2, v3::MigrateToV3, v4::MigrateToV4, v5::MigrateToV5, v6::MigrateToV6,
+  };
+  use frame_support::traits::OnRuntimeUpgrade;
+
+  fn run<M: OnRuntimeUpgrade>() {
+    #[cfg(feature = "try-runtime")]
+    let state = M::pre_upgrade().unwrap();
+    M::on_runtime_upgrade();
+    #[cfg(feature = "try-runtime")]
+    M::post_upgrade(state).unwrap();
+  }
+
+  run::<MigrateToV2<Test>>();
+  run::<MigrateToV3<Test>>();
+  run::<MigrateToV4<Test>>();
+  run::<MigrateToV5<Test>>();
+  run::<MigrateToV6<Test, KittyPalletId>>();
+}
+
+#[test]
+fn migrate_from_v0_works() {
+  use crate::migrations::v2::{v0_Kitty, UNNAMED};
+  use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(0).put::<KittiesModule>();
+    for kitty_id in 0u32..3 {
+      put_old_kitty(kitty_id, v0_Kitty([kitty_id as u8 + 1; 16]));
+      crate::KittyOwner::<Test>::insert(kitty_id, 1 + kitty_id as u64 % 2);
+    }
+    crate::KittyParents::<Test>::insert(2, (0, 1));
+
+    run_migrations();
+
+    assert_eq!(KittiesModule::on_chain_storage_version(), 6);
+    for kitty_id in 0u32..3 {
+      let kitty = KittiesModule::kitties(kitty_id).unwrap();
+      assert_eq!(kitty.dna, [kitty_id as u8 + 1; 16]); // dna不变
+      assert_eq!(kitty.name, UNNAMED);
+    }
+    assert_eq!(KittiesModule::kitties(2).unwrap().generation, 1);
+    assert_eq!(KittiesModule::kitties(0).unwrap().offspring, 1);
+    let mut owned = KittiesModule::owned_kitties(1).into_inner();
+    owned.sort();
+    assert_eq!(owned, vec![0, 2]);
+    assert_eq!(KittiesModule::owned_kitties(2).into_inner(), vec![1]);
+
+    // 再次执行不会改变数据
+    let kitties: Vec<_> = crate::Kitties::<Test>::iter().collect();
+    run_migrations();
+    let mut again: Vec<_> = crate::Kitties::<Test>::iter().collect();
+    let mut kitties = kitties;
+    kitties.sort_by_key(|(kitty_id, _)| *kitty_id);
+    again.sort_by_key(|(kitty_id, _)| *kitty_id);
+    assert_eq!(kitties, again);
+    assert_eq!(KittiesModule::owned_kitties(1).len(), 2);
+  })
+}
+
+#[test]
+fn migrate_from_v1_keeps_names() {
+  use crate::migrations::v2::v1_Kitty;
+  use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(1).put::<KittiesModule>();
+    put_old_kitty(0, v1_Kitty { dna: [7; 16], name: *b"abcd" });
+    put_old_kitty(1, v1_Kitty { dna: [8; 16], name: *b"wxyz" });
+    crate::KittyOwner::<Test>::insert(0, 1);
+    crate::KittyOwner::<Test>::insert(1, 1);
+
+    run_migrations();
+
+    assert_eq!(KittiesModule::on_chain_storage_version(), 6);
+    let kitty = KittiesModule::kitties(0).unwrap();
+    assert_eq!((kitty.dna, kitty.name), ([7; 16], *b"abcd\0\0\0\0"));
+    let kitty = KittiesModule::kitties(1).unwrap();
+    assert_eq!((kitty.dna, kitty.name), ([8; 16], *b"wxyz\0\0\0\0"));
+    let mut owned = KittiesModule::owned_kitties(1).into_inner();
+    owned.sort();
+    assert_eq!(owned, vec![0, 1]);
+  })
+}
+
+#[test]
+fn migrations_skip_current_version() {
+  use frame_support::traits::GetStorageVersion;
+
+  new_test_ext().execute_with(|| {
+    // 新链的存储版本就是当前版本
+    frame_support::traits::StorageVersion::new(6).put::<KittiesModule>();
+    let _res = Balances::force_set_balance(RuntimeOrigin::root(), 1, 1_000_000_000);
+    assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), *b"abcdefgg"));
+    let kitty = KittiesModule::kitties(0).unwrap();
+
+    run_migrations();
+
+    assert_eq!(KittiesModule::kitties(0), Some(kitty));
+    assert_eq!(KittiesModule::on_chain_storage_version(), 6);
+  })
+}
@@ -0,0 +1,445 @@
+//! Weights for pallet_kitties
+//!
+//! This file is a hand-written placeholder, NOT the output of `benchmark pallet`. The values are
+//! estimated from the storage accesses of each call and have not been measured. Replace
+//! the whole file with the generated output before relying on these weights in production:
+//!
+//! ```text
+//! ./scripts/benchmark.sh kitties
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn breed() -> Weight;
+	fn transfer() -> Weight;
+	fn sale() -> Weight;
+	fn buy() -> Weight;
+	fn cancel_sale() -> Weight;
+	fn update_listing() -> Weight;
+	fn create_auction() -> Weight;
+	fn bid() -> Weight;
+	fn cancel_auction() -> Weight;
+	fn release() -> Weight;
+	fn offer_sire() -> Weight;
+	fn withdraw_sire() -> Weight;
+	fn breed_with_sire() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from() -> Weight;
+	fn settle_auctions(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: RandomnessModule RandomMaterial (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: KittiesModule Kitties (r:0 w:1)
+	/// Storage: KittiesModule KittyOwner (r:0 w:1)
+	/// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	fn create() -> Weight {
+		Weight::from_parts(52_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:2 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:2 w:0)
+	/// Storage: KittiesModule Auctions (r:2 w:0)
+	/// Storage: KittiesModule Kitties (r:2 w:3)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: RandomnessModule RandomMaterial (r:1 w:0)
+	/// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	fn breed() -> Weight {
+		Weight::from_parts(78_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:0 w:1)
+	fn transfer() -> Weight {
+		Weight::from_parts(64_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	fn sale() -> Weight {
+		Weight::from_parts(24_000_000, 3_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn buy() -> Weight {
+		Weight::from_parts(86_000_000, 7_500)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	fn cancel_sale() -> Weight {
+		Weight::from_parts(20_000_000, 2_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	fn update_listing() -> Weight {
+		Weight::from_parts(21_000_000, 2_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	fn create_auction() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:0 w:1)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	fn bid() -> Weight {
+		Weight::from_parts(95_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	fn cancel_auction() -> Weight {
+		Weight::from_parts(25_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule Kitties (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:0 w:1)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn release() -> Weight {
+		Weight::from_parts(58_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	fn offer_sire() -> Weight {
+		Weight::from_parts(18_000_000, 2_500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule SiresForRent (r:1 w:1)
+	fn withdraw_sire() -> Weight {
+		Weight::from_parts(20_000_000, 2_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:2 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:2 w:0)
+	/// Storage: KittiesModule Auctions (r:2 w:0)
+	/// Storage: KittiesModule SiresForRent (r:1 w:0)
+	/// Storage: KittiesModule Kitties (r:2 w:3)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: RandomnessModule RandomMaterial (r:1 w:0)
+	/// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	fn breed_with_sire() -> Weight {
+		Weight::from_parts(105_000_000, 8_500)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_parts(20_000_000, 3_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:1 w:1)
+	/// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:0 w:1)
+	fn transfer_from() -> Weight {
+		Weight::from_parts(70_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:64 w:64)
+	/// Storage: System Account (r:128 w:128)
+	/// Storage: KittiesModule OwnedKitties (r:128 w:128)
+	/// Storage: KittiesModule KittyDeposits (r:64 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:64 w:64)
+	/// Storage: KittiesModule KittyOwner (r:0 w:64)
+	/// Storage: KittiesModule SiresForRent (r:0 w:64)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:64)
+	/// The range of component `n` is `[0, 64]`.
+	fn settle_auctions(n: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 1_500)
+			.saturating_add(Weight::from_parts(80_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((9_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7_500).saturating_mul(n.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: RandomnessModule RandomMaterial (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: KittiesModule Kitties (r:0 w:1)
+	/// Storage: KittiesModule KittyOwner (r:0 w:1)
+	/// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	fn create() -> Weight {
+		Weight::from_parts(52_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:2 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:2 w:0)
+	/// Storage: KittiesModule Auctions (r:2 w:0)
+	/// Storage: KittiesModule Kitties (r:2 w:3)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: RandomnessModule RandomMaterial (r:1 w:0)
+	/// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	fn breed() -> Weight {
+		Weight::from_parts(78_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:0 w:1)
+	fn transfer() -> Weight {
+		Weight::from_parts(64_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	fn sale() -> Weight {
+		Weight::from_parts(24_000_000, 3_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn buy() -> Weight {
+		Weight::from_parts(86_000_000, 7_500)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	fn cancel_sale() -> Weight {
+		Weight::from_parts(20_000_000, 2_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	fn update_listing() -> Weight {
+		Weight::from_parts(21_000_000, 2_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	fn create_auction() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:0 w:1)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:1)
+	fn bid() -> Weight {
+		Weight::from_parts(95_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	fn cancel_auction() -> Weight {
+		Weight::from_parts(25_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule Kitties (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:0 w:1)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn release() -> Weight {
+		Weight::from_parts(58_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	fn offer_sire() -> Weight {
+		Weight::from_parts(18_000_000, 2_500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule SiresForRent (r:1 w:1)
+	fn withdraw_sire() -> Weight {
+		Weight::from_parts(20_000_000, 2_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:2 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:2 w:0)
+	/// Storage: KittiesModule Auctions (r:2 w:0)
+	/// Storage: KittiesModule SiresForRent (r:1 w:0)
+	/// Storage: KittiesModule Kitties (r:2 w:3)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: RandomnessModule RandomMaterial (r:1 w:0)
+	/// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	fn breed_with_sire() -> Weight {
+		Weight::from_parts(105_000_000, 8_500)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_parts(20_000_000, 3_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:1 w:1)
+	/// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule KittyDeposits (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule SiresForRent (r:0 w:1)
+	/// Storage: KittiesModule KittiesOnSale (r:0 w:1)
+	fn transfer_from() -> Weight {
+		Weight::from_parts(70_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: KittiesModule AuctionsEndingAt (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:64 w:64)
+	/// Storage: System Account (r:128 w:128)
+	/// Storage: KittiesModule OwnedKitties (r:128 w:128)
+	/// Storage: KittiesModule KittyDeposits (r:64 w:0)
+	/// Storage: KittiesModule KittiesOnSale (r:64 w:64)
+	/// Storage: KittiesModule KittyOwner (r:0 w:64)
+	/// Storage: KittiesModule SiresForRent (r:0 w:64)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:64)
+	/// The range of component `n` is `[0, 64]`.
+	fn settle_auctions(n: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 1_500)
+			.saturating_add(Weight::from_parts(80_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((9_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7_500).saturating_mul(n.into()))
+	}
+}
@@ -0,0 +1,177 @@
+//! 可以离线校验的存证回执
+//!
+//! 回执由RPC `poe_claimReceipt`生成，包含存证所在区块的区块头、证明这个区块已经被finalize的
+//! GRANDPA finality证明，以及`Proofs`中这条存证的存储证明。校验时只需要一组可信的GRANDPA
+//! 验证人集合，不需要连接节点:
+//!
+//! 1. 区块头的哈希和回执中的区块哈希一致
+//! 2. GRANDPA justification由可信的验证人集合签名，并且回执的区块是被finalize的区块或者它的祖先
+//! 3. 存储证明的根是区块头中的state root，证明中有这条存证
+//! 4. 存证在回执的区块没有过期
+//!
+//! 区块头和存证按照node-template-runtime的类型解码: 区块号是`u32`，`AccountId`是`AccountId32`，
+//! `pallet_timestamp`的时间是`u64`。其他runtime只有在这些类型一致时才能用这个crate校验回执。
+use codec::{Decode, Encode};
+use pallet_poe::{ClaimMetadata, ContentHash, Proof};
+use sc_consensus_grandpa::{FinalityProof, GrandpaJustification};
+use serde::{Deserialize, Serialize};
+use sp_consensus_grandpa::AuthorityList;
+use sp_core::{
+	hashing::{blake2_128, twox_128},
+	Bytes, H256,
+};
+use sp_runtime::{
+	traits::{BlakeTwo256, ConstU32, Header as HeaderT},
+	AccountId32, OpaqueExtrinsic,
+};
+use sp_state_machine::{read_proof_check, StorageProof};
+
+#[cfg(test)]
+mod tests;
+
+/// 和node-template-runtime的opaque区块头一致，区块号是u32
+pub type Header = sp_runtime::generic::Header<u32, BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, OpaqueExtrinsic>;
+
+/// 只用来解码，不限制描述信息的长度
+type Unbounded = ConstU32<{ u32::MAX }>;
+
+/// 链上保存的存证，类型和node-template-runtime一致: (AccountId, BlockNumber, Moment)
+/// 分别是AccountId32、u32和u64，runtime修改这些类型后旧的回执无法解码
+pub type ClaimProof = Proof<AccountId32, u32, u64, ClaimMetadata<Unbounded, Unbounded, Unbounded>>;
+
+/// runtime中poe pallet的名字
+pub const DEFAULT_PALLET_NAME: &str = "PoeModule";
+
+/// 存证回执，所有二进制字段都是SCALE编码
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimReceipt<Hash> {
+	pub claim_hash: ContentHash,
+	/// 存证所在的区块
+	pub block_hash: Hash,
+	pub header: Bytes,
+	/// `sc_consensus_grandpa::FinalityProof`，证明block_hash或者它之后的区块已经被finalize
+	pub finality_proof: Bytes,
+	/// `Proofs`中这条存证的存储证明
+	pub storage_proof: Vec<Bytes>,
+}
+
+/// 可信的GRANDPA验证人集合，可以从任意可信的节点或者区块浏览器获得
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthoritySet {
+	pub set_id: u64,
+	/// (验证人, 权重)
+	pub authorities: AuthorityList,
+}
+
+/// 校验通过的存证
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct VerifiedClaim {
+	pub block_number: u32,
+	pub block_hash: H256,
+	pub proof: ClaimProof,
+}
+
+#[derive(Debug)]
+pub enum Error {
+	/// 区块头无法解码或者和区块哈希不一致
+	InvalidHeader,
+	InvalidFinalityProof,
+	/// justification不是由可信的验证人集合签名的
+	InvalidJustification(String),
+	/// 回执的区块不在被finalize的链上
+	NotFinalized,
+	InvalidStorageProof(String),
+	/// 区块中没有这条存证
+	ClaimNotFound,
+	/// 存证在回执的区块已经过期，只是还没有被清理
+	ClaimExpired,
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Error::InvalidHeader => write!(f, "header does not match the block hash"),
+			Error::InvalidFinalityProof => write!(f, "invalid finality proof"),
+			Error::InvalidJustification(e) => write!(f, "invalid justification: {}", e),
+			Error::NotFinalized => write!(f, "block is not an ancestor of the finalized block"),
+			Error::InvalidStorageProof(e) => write!(f, "invalid storage proof: {}", e),
+			Error::ClaimNotFound => write!(f, "claim is not in the block state"),
+			Error::ClaimExpired => write!(f, "claim had expired at the receipt block"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// 存证在`Proofs`中的存储key，和`Proofs::<T>::hashed_key_for`一致
+pub fn proof_storage_key(pallet_name: &str, claim_hash: &ContentHash) -> Vec<u8> {
+	let encoded = claim_hash.encode();
+	let mut key = twox_128(pallet_name.as_bytes()).to_vec();
+	key.extend(twox_128(b"Proofs"));
+	key.extend(blake2_128(&encoded));
+	key.extend(encoded);
+	key
+}
+
+/// 用可信的验证人集合校验回执，pallet_name是runtime中poe pallet的名字
+pub fn verify(
+	receipt: &ClaimReceipt<H256>,
+	authority_set: &AuthoritySet,
+	pallet_name: &str,
+) -> Result<VerifiedClaim, Error> {
+	let header = Header::decode(&mut &receipt.header[..]).map_err(|_| Error::InvalidHeader)?;
+	if header.hash() != receipt.block_hash {
+		return Err(Error::InvalidHeader)
+	}
+
+	verify_finality(receipt.block_hash, &receipt.finality_proof, authority_set)?;
+
+	let key = proof_storage_key(pallet_name, &receipt.claim_hash);
+	let storage_proof = StorageProof::new(receipt.storage_proof.iter().map(|node| node.to_vec()));
+	let mut values = read_proof_check::<BlakeTwo256, _>(*header.state_root(), storage_proof, [&key])
+		.map_err(|e| Error::InvalidStorageProof(e.to_string()))?;
+	let value = values.remove(&key).flatten().ok_or(Error::ClaimNotFound)?;
+	let proof = ClaimProof::decode(&mut &value[..])
+		.map_err(|e| Error::InvalidStorageProof(e.to_string()))?;
+	// 和pallet一样，过期区块及之后的存证都已经失效
+	if proof.expires_at.map_or(false, |expires_at| expires_at <= *header.number()) {
+		return Err(Error::ClaimExpired)
+	}
+
+	Ok(VerifiedClaim { block_number: *header.number(), block_hash: receipt.block_hash, proof })
+}
+
+/// 校验block_hash已经被authority_set finalize
+fn verify_finality(
+	block_hash: H256,
+	encoded: &[u8],
+	authority_set: &AuthoritySet,
+) -> Result<(), Error> {
+	let finality_proof = FinalityProof::<Header>::decode(&mut &encoded[..])
+		.map_err(|_| Error::InvalidFinalityProof)?;
+	let justification =
+		GrandpaJustification::<Block>::decode(&mut &finality_proof.justification[..])
+			.map_err(|_| Error::InvalidFinalityProof)?;
+	justification
+		.verify(authority_set.set_id, &authority_set.authorities)
+		.map_err(|e| Error::InvalidJustification(e.to_string()))?;
+	if justification.target().1 != finality_proof.block {
+		return Err(Error::InvalidFinalityProof)
+	}
+
+	// unknown_headers是从回执的下一个区块到被finalize的区块的区块头
+	let mut hash = block_hash;
+	for header in &finality_proof.unknown_headers {
+		if *header.parent_hash() != hash {
+			return Err(Error::NotFinalized)
+		}
+		hash = header.hash();
+	}
+	if hash != finality_proof.block {
+		return Err(Error::NotFinalized)
+	}
+	Ok(())
+}
@@ -0,0 +1,45 @@
+//! 离线校验存证回执
+//!
+//! 用法: poe-verify-receipt <回执.json> <验证人集合.json> [pallet名字]
+//!
+//! 回执是RPC `poe_claimReceipt`的返回结果，验证人集合的格式为
+//! `{"setId": 0, "authorities": [["<ss58地址>", 1]]}`。
+use std::{fs, process};
+
+use pallet_poe_receipt::{verify, AuthoritySet, ClaimReceipt, DEFAULT_PALLET_NAME};
+use serde::de::DeserializeOwned;
+use sp_core::H256;
+
+const USAGE: &str = "usage: poe-verify-receipt <receipt.json> <authority-set.json> [pallet]";
+
+fn read_json<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+	let content = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+	serde_json::from_slice(&content).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+	let (receipt_path, authority_set_path) = match args {
+		[receipt, authority_set] | [receipt, authority_set, _] => (receipt, authority_set),
+		_ => return Err(USAGE.into()),
+	};
+	let pallet_name = args.get(2).map_or(DEFAULT_PALLET_NAME, String::as_str);
+
+	let receipt: ClaimReceipt<H256> = read_json(receipt_path)?;
+	let authority_set: AuthoritySet = read_json(authority_set_path)?;
+	let claim = verify(&receipt, &authority_set, pallet_name).map_err(|e| e.to_string())?;
+
+	println!("claim:      {:?}", receipt.claim_hash);
+	println!("block:      #{} ({:?})", claim.block_number, claim.block_hash);
+	println!("owner:      {}", claim.proof.owner);
+	println!("created at: {}", claim.proof.created_at);
+	Ok(())
+}
+
+fn main() {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	if let Err(e) = run(&args) {
+		eprintln!("verification failed: {}", e);
+		process::exit(1);
+	}
+	println!("receipt verified");
+}
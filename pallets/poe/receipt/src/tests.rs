@@ -0,0 +1,177 @@
+use super::*;
+use finality_grandpa::{Commit, Message, Precommit, SignedPrecommit};
+use pallet_poe::HashAlgorithm;
+use sp_consensus_grandpa::{localized_payload, AuthorityId, AuthoritySignature};
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::{generic::Digest, StateVersion};
+use sp_state_machine::{prove_read, InMemoryBackend};
+
+const SET_ID: u64 = 1;
+const ROUND: u64 = 7;
+
+fn claim_hash() -> ContentHash {
+  ContentHash::of(HashAlgorithm::Blake2_256, b"contract")
+}
+
+fn claim(expires_at: Option<u32>) -> ClaimProof {
+  ClaimProof {
+    owner: Ed25519Keyring::Alice.to_account_id(),
+    block_number: 1,
+    created_at: 1_000,
+    metadata: ClaimMetadata {
+      description: b"contract".to_vec().try_into().unwrap(),
+      mime_type: b"application/pdf".to_vec().try_into().unwrap(),
+      uri: None,
+    },
+    expires_at,
+  }
+}
+
+// 只有key => value一条数据的状态，返回(state root, 存储证明)
+fn state(key: &[u8], value: Vec<u8>) -> (H256, Vec<Bytes>) {
+  let backend = InMemoryBackend::<BlakeTwo256>::from((
+    vec![(None, vec![(key.to_vec(), Some(value))])],
+    StateVersion::V1,
+  ));
+  let root = *backend.root();
+  let proof = prove_read(backend, [key]).unwrap();
+  (root, proof.into_iter_nodes().map(Bytes).collect())
+}
+
+fn header(number: u32, parent_hash: H256, state_root: H256) -> Header {
+  Header::new(number, Default::default(), state_root, parent_hash, Digest::default())
+}
+
+// signer签名的finalize target的justification，编码和`GrandpaJustification`一致
+fn finality_proof(target: &Header, unknown_headers: Vec<Header>, signer: Ed25519Keyring) -> Bytes {
+  let precommit = Precommit { target_hash: target.hash(), target_number: *target.number() };
+  let payload = localized_payload(ROUND, SET_ID, &Message::Precommit(precommit.clone()));
+  let signed: SignedPrecommit<H256, u32, AuthoritySignature, AuthorityId> = SignedPrecommit {
+    precommit,
+    signature: signer.sign(&payload).into(),
+    id: signer.public().into(),
+  };
+  let commit = Commit {
+    target_hash: target.hash(),
+    target_number: *target.number(),
+    precommits: vec![signed],
+  };
+  let justification = (ROUND, commit, Vec::<Header>::new()).encode();
+
+  FinalityProof { block: target.hash(), justification, unknown_headers }.encode().into()
+}
+
+fn authority_set(authority: Ed25519Keyring) -> AuthoritySet {
+  AuthoritySet { set_id: SET_ID, authorities: vec![(authority.public().into(), 1)] }
+}
+
+// 存证在区块1，区块2被Alice finalize
+fn signed_receipt(value: Vec<u8>) -> ClaimReceipt<H256> {
+  let (state_root, storage_proof) =
+    state(&proof_storage_key(DEFAULT_PALLET_NAME, &claim_hash()), value);
+  let block = header(1, H256::repeat_byte(1), state_root);
+  let finalized = header(2, block.hash(), H256::repeat_byte(2));
+
+  ClaimReceipt {
+    claim_hash: claim_hash(),
+    block_hash: block.hash(),
+    header: block.encode().into(),
+    finality_proof: finality_proof(&finalized, vec![finalized.clone()], Ed25519Keyring::Alice),
+    storage_proof,
+  }
+}
+
+fn verify_receipt(receipt: &ClaimReceipt<H256>) -> Result<VerifiedClaim, Error> {
+  verify(receipt, &authority_set(Ed25519Keyring::Alice), DEFAULT_PALLET_NAME)
+}
+
+#[test]
+fn verify_works() {
+  let receipt = signed_receipt(claim(None).encode());
+
+  let verified = verify_receipt(&receipt).unwrap();
+  assert_eq!(verified.block_number, 1);
+  assert_eq!(verified.block_hash, receipt.block_hash);
+  assert_eq!(verified.proof, claim(None));
+}
+
+#[test]
+fn verify_works_when_block_is_finalized() {
+  let mut receipt = signed_receipt(claim(None).encode());
+  let block = Header::decode(&mut &receipt.header[..]).unwrap();
+  receipt.finality_proof = finality_proof(&block, vec![], Ed25519Keyring::Alice);
+
+  assert_eq!(verify_receipt(&receipt).unwrap().proof, claim(None));
+}
+
+#[test]
+fn tampered_header_is_rejected() {
+  let mut receipt = signed_receipt(claim(None).encode());
+  let mut block = Header::decode(&mut &receipt.header[..]).unwrap();
+  block.state_root = H256::repeat_byte(3);
+  receipt.header = block.encode().into();
+
+  assert!(matches!(verify_receipt(&receipt), Err(Error::InvalidHeader)));
+}
+
+#[test]
+fn wrong_authority_set_is_rejected() {
+  let receipt = signed_receipt(claim(None).encode());
+
+  let result = verify(&receipt, &authority_set(Ed25519Keyring::Bob), DEFAULT_PALLET_NAME);
+  assert!(matches!(result, Err(Error::InvalidJustification(_))));
+
+  let wrong_set_id = AuthoritySet { set_id: SET_ID + 1, ..authority_set(Ed25519Keyring::Alice) };
+  let result = verify(&receipt, &wrong_set_id, DEFAULT_PALLET_NAME);
+  assert!(matches!(result, Err(Error::InvalidJustification(_))));
+}
+
+#[test]
+fn broken_unknown_headers_are_rejected() {
+  let mut receipt = signed_receipt(claim(None).encode());
+  // 被finalize的区块不是回执的区块的子区块
+  let finalized = header(2, H256::repeat_byte(4), H256::repeat_byte(2));
+  receipt.finality_proof =
+    finality_proof(&finalized, vec![finalized.clone()], Ed25519Keyring::Alice);
+  assert!(matches!(verify_receipt(&receipt), Err(Error::NotFinalized)));
+
+  // unknown_headers没有连到被finalize的区块
+  let block = Header::decode(&mut &receipt.header[..]).unwrap();
+  let child = header(2, block.hash(), H256::repeat_byte(2));
+  let finalized = header(3, H256::repeat_byte(5), H256::repeat_byte(3));
+  receipt.finality_proof =
+    finality_proof(&finalized, vec![child, finalized.clone()], Ed25519Keyring::Alice);
+  assert!(matches!(verify_receipt(&receipt), Err(Error::NotFinalized)));
+}
+
+#[test]
+fn mismatched_storage_proof_is_rejected() {
+  // 存储证明来自另一个状态，和区块头的state root对不上
+  let mut receipt = signed_receipt(claim(None).encode());
+  let key = proof_storage_key(DEFAULT_PALLET_NAME, &claim_hash());
+  receipt.storage_proof = state(&key, claim(Some(10)).encode()).1;
+  assert!(matches!(verify_receipt(&receipt), Err(Error::InvalidStorageProof(_))));
+
+  // 状态中没有这个摘要的存证
+  let mut receipt = signed_receipt(claim(None).encode());
+  receipt.claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"contract");
+  assert!(matches!(
+    verify_receipt(&receipt),
+    Err(Error::ClaimNotFound) | Err(Error::InvalidStorageProof(_))
+  ));
+
+  // 在其他pallet名字下查找
+  let receipt = signed_receipt(claim(None).encode());
+  let result = verify(&receipt, &authority_set(Ed25519Keyring::Alice), "Kitties");
+  assert!(matches!(result, Err(Error::ClaimNotFound) | Err(Error::InvalidStorageProof(_))));
+}
+
+#[test]
+fn expired_claim_is_rejected() {
+  // 在回执的区块开始时过期
+  let receipt = signed_receipt(claim(Some(1)).encode());
+  assert!(matches!(verify_receipt(&receipt), Err(Error::ClaimExpired)));
+
+  let receipt = signed_receipt(claim(Some(2)).encode());
+  assert_eq!(verify_receipt(&receipt).unwrap().proof, claim(Some(2)));
+}
@@ -0,0 +1,186 @@
+//! poe pallet的RPC接口，所有方法都以`poe_`开头
+//!
+//! `poe_claimReceipt`返回的回执可以用`pallet-poe-receipt`离线校验。
+
+use std::sync::Arc;
+
+use codec::{Codec, Encode};
+use finality_grandpa::BlockNumberOps;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sc_client_api::{Backend, ProofProvider};
+use sc_consensus_grandpa::FinalityProofProvider;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
+
+pub use pallet_poe_runtime_api::PoeApi as PoeRuntimeApi;
+use pallet_poe_receipt::ClaimReceipt;
+use pallet_poe_runtime_api::{ClaimStatus, ContentHash, CustodyRecord, MerkleProof};
+
+#[rpc(client, server)]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber> {
+	#[method(name = "poe_verifyInclusion")]
+	fn verify_inclusion(
+		&self,
+		leaf: H256,
+		proof: MerkleProof,
+		root: H256,
+		at: Option<BlockHash>,
+	) -> RpcResult<bool>;
+
+	#[method(name = "poe_custodyHistory")]
+	fn custody_history(
+		&self,
+		claim_hash: ContentHash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<CustodyRecord<AccountId, BlockNumber>>>;
+
+	#[method(name = "poe_claimStatus")]
+	fn claim_status(
+		&self,
+		claim_hash: ContentHash,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ClaimStatus>>;
+
+	/// 存证在at区块(默认是最新finalize的区块)的回执，区块必须已经被finalize
+	#[method(name = "poe_claimReceipt")]
+	fn claim_receipt(
+		&self,
+		claim_hash: ContentHash,
+		at: Option<BlockHash>,
+	) -> RpcResult<ClaimReceipt<BlockHash>>;
+}
+
+/// poe RPC的实现
+pub struct Poe<C, B, Block: BlockT> {
+	client: Arc<C>,
+	/// 生成回执中的GRANDPA finality证明
+	finality_provider: Arc<FinalityProofProvider<B, Block>>,
+}
+
+impl<C, B, Block: BlockT> Poe<C, B, Block> {
+	pub fn new(client: Arc<C>, finality_provider: Arc<FinalityProofProvider<B, Block>>) -> Self {
+		Self { client, finality_provider }
+	}
+}
+
+/// 调用runtime api出错
+const RUNTIME_ERROR: i32 = 1;
+/// 无法生成回执
+const RECEIPT_ERROR: i32 = 2;
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(format!("{:?}", e)))).into()
+}
+
+fn receipt_error(message: &str) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(RECEIPT_ERROR, message, None::<()>)).into()
+}
+
+impl<C, B, Block, AccountId, BlockNumber>
+	PoeApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber> for Poe<C, B, Block>
+where
+	Block: BlockT,
+	NumberFor<Block>: BlockNumberOps,
+	B: Backend<Block> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + ProofProvider<Block>,
+	C: Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec + Send + Sync + 'static + Serialize + DeserializeOwned,
+	BlockNumber: Codec + Send + Sync + 'static + Serialize + DeserializeOwned,
+{
+	fn verify_inclusion(
+		&self,
+		leaf: H256,
+		proof: MerkleProof,
+		root: H256,
+		at: Option<Block::Hash>,
+	) -> RpcResult<bool> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.verify_inclusion(at, leaf, proof, root)
+			.map_err(|e| runtime_error("Unable to verify inclusion.", e))
+	}
+
+	fn custody_history(
+		&self,
+		claim_hash: ContentHash,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<CustodyRecord<AccountId, BlockNumber>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.custody_history(at, claim_hash)
+			.map_err(|e| runtime_error("Unable to query custody history.", e))
+	}
+
+	fn claim_status(
+		&self,
+		claim_hash: ContentHash,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ClaimStatus>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.claim_status(at, claim_hash)
+			.map_err(|e| runtime_error("Unable to query claim status.", e))
+	}
+
+	fn claim_receipt(
+		&self,
+		claim_hash: ContentHash,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ClaimReceipt<Block::Hash>> {
+		let info = self.client.info();
+		let at = at.unwrap_or(info.finalized_hash);
+		let header = self
+			.client
+			.header(at)
+			.map_err(|e| runtime_error("Unable to query header.", e))?
+			.ok_or_else(|| receipt_error("Unknown block."))?;
+		// 只有finalize的链上的区块才能证明
+		let number = *header.number();
+		let canonical = self.client.hash(number).ok().flatten() == Some(at);
+		if number > info.finalized_number || !canonical {
+			return Err(receipt_error("Block is not finalized."))
+		}
+
+		let api = self.client.runtime_api();
+		let status = api
+			.claim_status(at, claim_hash)
+			.map_err(|e| runtime_error("Unable to query claim status.", e))?;
+		if status != Some(ClaimStatus::Active) {
+			return Err(receipt_error("Claim does not exist at this block."))
+		}
+		let key = api
+			.proof_storage_key(at, claim_hash)
+			.map_err(|e| runtime_error("Unable to query storage key.", e))?;
+		let storage_proof = self
+			.client
+			.read_proof(at, &mut std::iter::once(key.as_slice()))
+			.map_err(|e| runtime_error("Unable to prove storage.", e))?;
+
+		// 每个验证人集合的最后一个区块和每justification_period个区块才会保存justification，
+		// 在这之前无法证明
+		let finality_proof = self
+			.finality_provider
+			.prove_finality(number)
+			.map_err(|e| runtime_error("Unable to prove finality.", e))?
+			.ok_or_else(|| receipt_error("No justification covers this block yet."))?;
+
+		Ok(ClaimReceipt {
+			claim_hash,
+			block_hash: at,
+			header: header.encode().into(),
+			finality_proof: finality_proof.into(),
+			storage_proof: storage_proof.into_iter_nodes().map(Into::into).collect(),
+		})
+	}
+}
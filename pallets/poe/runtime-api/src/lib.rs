@@ -0,0 +1,29 @@
+//! poe pallet的runtime api，给RPC查询存证
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::H256;
+use sp_std::vec::Vec;
+
+pub use pallet_poe::{ClaimStatus, ContentHash, CustodyAction, CustodyRecord, MerkleProof};
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// leaf是否包含在以root为根的批量存证中
+		fn verify_inclusion(leaf: H256, proof: MerkleProof, root: H256) -> bool;
+
+		/// 存证的所有权历史，从创建开始
+		fn custody_history(claim_hash: ContentHash) -> Vec<CustodyRecord<AccountId, BlockNumber>>;
+
+		/// 存证的状态，从来没有创建过时返回None
+		fn claim_status(claim_hash: ContentHash) -> Option<ClaimStatus>;
+
+		/// 存证在`Proofs`中的存储key
+		fn proof_storage_key(claim_hash: ContentHash) -> Vec<u8>;
+	}
+}
@@ -0,0 +1,342 @@
+//! Benchmarking setup for pallet-poe
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Poe;
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{Currency, Get, ReservableCurrency, Time},
+	BoundedVec,
+};
+use sp_runtime::traits::{Bounded, Zero};
+use frame_system::RawOrigin;
+use sp_std::{vec, vec::Vec};
+
+// 长度为l的存证
+fn claim<T: Config>(l: u32) -> BoundedVec<u8, T::MaxClaimLength> {
+	BoundedVec::try_from(vec![1u8; l as usize]).expect("l <= MaxClaimLength; qed")
+}
+
+// 链下计算好的摘要，和算法无关，使用sha2-256
+fn claim_hash() -> ContentHash {
+	ContentHash::of(HashAlgorithm::Sha2_256, b"benchmark")
+}
+
+// 第i个用来填充的存证
+fn nth_claim_hash(i: u32) -> ContentHash {
+	ContentHash::of(HashAlgorithm::Sha2_256, &i.encode())
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 1_000u32.into());
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	fund::<T>(&caller);
+	caller
+}
+
+// 所有字段都是最大长度的描述信息
+fn bounded<S: Get<u32>>() -> BoundedVec<u8, S> {
+	BoundedVec::try_from(vec![b'a'; S::get() as usize]).expect("length is S; qed")
+}
+
+fn metadata<T: Config>() -> ClaimMetadataOf<T> {
+	ClaimMetadata { description: bounded(), mime_type: bounded(), uri: Some(bounded()) }
+}
+
+// 插入在expires_at过期的存证，owner需要有足够的余额支付质押
+fn insert_proof<T: Config>(
+	claim_hash: ContentHash,
+	owner: T::AccountId,
+	expires_at: T::BlockNumber,
+) {
+	let proof = Proof {
+		owner,
+		block_number: frame_system::Pallet::<T>::block_number(),
+		created_at: T::Time::now(),
+		metadata: metadata::<T>(),
+		expires_at: Some(expires_at),
+	};
+	let record = Poe::<T>::created_record(&proof);
+	let deposit = Poe::<T>::deposit_for(&proof) + Poe::<T>::history_deposit(&record);
+	T::Currency::reserve(&proof.owner, deposit).expect("owner is funded; qed");
+	Proofs::<T>::insert(claim_hash, proof);
+	ClaimDeposits::<T>::insert(claim_hash, deposit);
+	Poe::<T>::start_history(&claim_hash, record);
+	ClaimsExpiringAt::<T>::try_mutate(expires_at, |hashes| hashes.try_push(claim_hash))
+		.expect("less than MaxExpiringPerBlock claims; qed");
+}
+
+// 把expires_at过期的存证填满，只留下一个位置，新的存证插入列表时需要最多的读写
+fn fill_expiring<T: Config>(expires_at: T::BlockNumber) {
+	let owner: T::AccountId = account("owner", 0, 0);
+	fund::<T>(&owner);
+	for i in 1..T::MaxExpiringPerBlock::get() {
+		insert_proof::<T>(nth_claim_hash(i), owner.clone(), expires_at);
+	}
+}
+
+// 现在创建的待签名存证的超时区块
+fn timeout_at<T: Config>() -> T::BlockNumber {
+	frame_system::Pallet::<T>::block_number() + T::PendingClaimTimeout::get()
+}
+
+// s个共同签名人
+fn cosigners<T: Config>(s: u32) -> BoundedVec<T::AccountId, T::MaxCosigners> {
+	let cosigners: Vec<T::AccountId> = (0..s).map(|i| account("cosigner", i, 0)).collect();
+	BoundedVec::try_from(cosigners).expect("s <= MaxCosigners; qed")
+}
+
+// 插入需要s个人全部签名、除最后一个人外都已经签名的存证
+fn insert_pending<T: Config>(claim_hash: ContentHash, owner: T::AccountId, s: u32) {
+	let cosigners = cosigners::<T>(s);
+	let approvals = cosigners[..cosigners.len() - 1].to_vec();
+	let timeout_at = timeout_at::<T>();
+	let pending = PendingClaim::<T> {
+		owner,
+		metadata: metadata::<T>(),
+		expires_at: Some(timeout_at),
+		cosigners,
+		threshold: s,
+		approvals: BoundedVec::try_from(approvals).expect("less than s approvals; qed"),
+		rejections: Default::default(),
+		timeout_at,
+		deposit: Poe::<T>::deposit_for_len(1_000),
+	};
+	T::Currency::reserve(&pending.owner, pending.deposit).expect("owner is funded; qed");
+	PendingClaims::<T>::insert(claim_hash, pending);
+	PendingTimeoutsAt::<T>::try_mutate(timeout_at, |hashes| hashes.try_push(claim_hash))
+		.expect("less than MaxPendingPerBlock claims; qed");
+}
+
+fn block<T: Config>(n: u32) -> T::BlockNumber {
+	n.into()
+}
+
+fn owner_of<T: Config>(claim_hash: ContentHash) -> Option<T::AccountId> {
+	Proofs::<T>::get(claim_hash).map(|proof| proof.owner)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create_claim(l: Linear<1, { T::MaxClaimLength::get() }>) {
+		let caller = funded_caller::<T>();
+		let claim = claim::<T>(l);
+		fill_expiring::<T>(block::<T>(10));
+
+		#[extrinsic_call]
+		create_claim(
+			RawOrigin::Signed(caller.clone()),
+			claim.clone(),
+			metadata::<T>(),
+			Some(block::<T>(10)),
+		);
+
+		let claim_hash = ContentHash::of(HashAlgorithm::Blake2_256, &claim);
+		assert_eq!(owner_of::<T>(claim_hash), Some(caller));
+	}
+
+	#[benchmark]
+	fn create_claim_for_hash() {
+		let caller = funded_caller::<T>();
+		let claim_hash = claim_hash();
+		fill_expiring::<T>(block::<T>(10));
+
+		#[extrinsic_call]
+		create_claim_for_hash(
+			RawOrigin::Signed(caller.clone()),
+			claim_hash,
+			metadata::<T>(),
+			Some(block::<T>(10)),
+		);
+
+		assert_eq!(owner_of::<T>(claim_hash), Some(caller));
+	}
+
+	#[benchmark]
+	fn revoke_claim() {
+		let caller = funded_caller::<T>();
+		let claim_hash = claim_hash();
+		fill_expiring::<T>(block::<T>(10));
+		insert_proof::<T>(claim_hash, caller.clone(), block::<T>(10));
+
+		#[extrinsic_call]
+		revoke_claim(RawOrigin::Signed(caller), claim_hash, u16::MAX, bounded());
+
+		assert!(!Proofs::<T>::contains_key(claim_hash));
+		assert!(Tombstones::<T>::contains_key(claim_hash));
+	}
+
+	#[benchmark]
+	fn transfer_claim() {
+		let caller = funded_caller::<T>();
+		let dest: T::AccountId = account("dest", 0, 0);
+		fund::<T>(&dest);
+		let claim_hash = claim_hash();
+		insert_proof::<T>(claim_hash, caller.clone(), block::<T>(10));
+		// 历史记录只剩一个位置
+		ClaimHistory::<T>::mutate(claim_hash, |history| {
+			let record = history[0].clone();
+			while history.len() + 1 < T::MaxHistoryLength::get() as usize {
+				history.try_push(record.clone()).expect("less than MaxHistoryLength records; qed");
+			}
+		});
+
+		#[extrinsic_call]
+		transfer_claim(RawOrigin::Signed(caller), claim_hash, dest.clone());
+
+		assert_eq!(owner_of::<T>(claim_hash), Some(dest));
+		assert_eq!(ClaimHistory::<T>::get(claim_hash).len() as u32, T::MaxHistoryLength::get());
+	}
+
+	#[benchmark]
+	fn renew_claim() {
+		let caller = funded_caller::<T>();
+		let claim_hash = claim_hash();
+		// 从一个满的列表移到另一个只剩一个位置的列表
+		fill_expiring::<T>(block::<T>(10));
+		insert_proof::<T>(claim_hash, caller.clone(), block::<T>(10));
+		fill_expiring::<T>(block::<T>(20));
+
+		#[extrinsic_call]
+		renew_claim(RawOrigin::Signed(caller), claim_hash, Some(block::<T>(20)));
+
+		let expires_at = Proofs::<T>::get(claim_hash).and_then(|proof| proof.expires_at);
+		assert_eq!(expires_at, Some(block::<T>(20)));
+	}
+
+	#[benchmark]
+	fn expire_claims(n: Linear<0, { T::MaxExpiringPerBlock::get() }>) {
+		let owner: T::AccountId = account("owner", 0, 0);
+		fund::<T>(&owner);
+		let expires_at = block::<T>(1);
+		for i in 0..n {
+			insert_proof::<T>(nth_claim_hash(i), owner.clone(), expires_at);
+		}
+		NextExpiryBlock::<T>::put(expires_at);
+
+		#[block]
+		{
+			// 读取列表算一次
+			Poe::<T>::sweep_expired(expires_at, n + 1);
+		}
+
+		assert_eq!(Proofs::<T>::iter().count(), 0);
+		assert_eq!(NextExpiryBlock::<T>::get(), block::<T>(2));
+	}
+
+	#[benchmark]
+	fn anchor_batch() {
+		let caller = funded_caller::<T>();
+		let root = sp_core::H256::repeat_byte(1);
+
+		#[extrinsic_call]
+		anchor_batch(RawOrigin::Signed(caller.clone()), root, u32::MAX, metadata::<T>());
+
+		assert_eq!(Anchors::<T>::get(root).map(|anchor| anchor.owner), Some(caller));
+	}
+
+	#[benchmark]
+	fn remove_anchor() {
+		let caller = funded_caller::<T>();
+		let root = sp_core::H256::repeat_byte(1);
+		let origin = RawOrigin::Signed(caller.clone());
+		Poe::<T>::anchor_batch(origin.into(), root, u32::MAX, metadata::<T>())
+			.expect("caller has enough balance; qed");
+
+		#[extrinsic_call]
+		remove_anchor(RawOrigin::Signed(caller), root);
+
+		assert!(Anchors::<T>::get(root).is_none());
+	}
+
+	#[benchmark]
+	fn release_tombstone_deposit() {
+		let caller = funded_caller::<T>();
+		let claim_hash = claim_hash();
+		insert_proof::<T>(claim_hash, caller.clone(), block::<T>(10));
+		let origin = RawOrigin::Signed(caller.clone());
+		Poe::<T>::revoke_claim(origin.into(), claim_hash, u16::MAX, bounded())
+			.expect("caller owns the claim; qed");
+
+		#[extrinsic_call]
+		release_tombstone_deposit(RawOrigin::Signed(caller), claim_hash);
+
+		assert!(Tombstones::<T>::contains_key(claim_hash));
+		assert!(ClaimDeposits::<T>::get(claim_hash).is_zero());
+	}
+
+	#[benchmark]
+	fn create_pending_claim(s: Linear<1, { T::MaxCosigners::get() }>) {
+		let caller = funded_caller::<T>();
+		let claim_hash = claim_hash();
+		let expires_at = Some(timeout_at::<T>());
+
+		#[extrinsic_call]
+		create_pending_claim(
+			RawOrigin::Signed(caller.clone()),
+			claim_hash,
+			metadata::<T>(),
+			expires_at,
+			cosigners::<T>(s),
+			s,
+		);
+
+		assert_eq!(PendingClaims::<T>::get(claim_hash).map(|pending| pending.owner), Some(caller));
+	}
+
+	#[benchmark]
+	fn cosign_claim(s: Linear<1, { T::MaxCosigners::get() }>) {
+		let owner = funded_caller::<T>();
+		let claim_hash = claim_hash();
+		insert_pending::<T>(claim_hash, owner.clone(), s);
+		// 最后一个签名使存证生效，插入一个只剩一个位置的过期列表
+		fill_expiring::<T>(timeout_at::<T>());
+		let signer: T::AccountId = account("cosigner", s - 1, 0);
+
+		#[extrinsic_call]
+		cosign_claim(RawOrigin::Signed(signer), claim_hash);
+
+		assert_eq!(owner_of::<T>(claim_hash), Some(owner));
+	}
+
+	#[benchmark]
+	fn reject_claim(s: Linear<1, { T::MaxCosigners::get() }>) {
+		let owner = funded_caller::<T>();
+		let claim_hash = claim_hash();
+		insert_pending::<T>(claim_hash, owner, s);
+		// 需要所有人签名，一个人拒绝就删除
+		let signer: T::AccountId = account("cosigner", s - 1, 0);
+
+		#[extrinsic_call]
+		reject_claim(RawOrigin::Signed(signer), claim_hash);
+
+		assert!(!PendingClaims::<T>::contains_key(claim_hash));
+	}
+
+	#[benchmark]
+	fn time_out_pending_claims(n: Linear<0, { T::MaxPendingPerBlock::get() }>) {
+		let owner: T::AccountId = account("owner", 0, 0);
+		fund::<T>(&owner);
+		for i in 0..n {
+			insert_pending::<T>(nth_claim_hash(i), owner.clone(), T::MaxCosigners::get());
+		}
+		let timeout_at = timeout_at::<T>();
+
+		#[block]
+		{
+			Poe::<T>::time_out_pending_claims(timeout_at);
+		}
+
+		assert_eq!(PendingClaims::<T>::iter().count(), 0);
+	}
+
+	impl_benchmark_test_suite!(Poe, crate::mock::new_test_ext(), crate::mock::Test);
+}
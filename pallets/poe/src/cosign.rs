@@ -0,0 +1,149 @@
+//! 需要多方共同签名的存证
+//!
+//! 存证先以待签名的状态保存在`PendingClaims`中，共同签名人中有threshold个人签名后才写入`Proofs`。
+//! 拒绝的人多到不可能再达到threshold，或者`PendingClaimTimeout`个区块内没有完成签名时，
+//! 待签名的存证被删除，质押退回给owner。超时的存证记录在`PendingTimeoutsAt`中，
+//! 在超时区块的on_initialize中删除。
+use crate::*;
+use frame_support::{pallet_prelude::*, traits::ReservableCurrency};
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::prelude::*;
+
+impl<T: Config> Pallet<T> {
+	pub(crate) fn do_create_pending_claim(
+		sender: T::AccountId,
+		claim_hash: ContentHash,
+		metadata: ClaimMetadataOf<T>,
+		expires_at: Option<T::BlockNumber>,
+		cosigners: BoundedVec<T::AccountId, T::MaxCosigners>,
+		threshold: u32,
+	) -> DispatchResult {
+		Self::ensure_claimable(&claim_hash)?;
+		ensure!(
+			threshold > 0 && threshold as usize <= cosigners.len(),
+			Error::<T>::InvalidThreshold
+		);
+		let mut distinct = cosigners.to_vec();
+		distinct.sort();
+		distinct.dedup();
+		ensure!(distinct.len() == cosigners.len(), Error::<T>::DuplicateCosigner);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let timeout_at = now.saturating_add(T::PendingClaimTimeout::get());
+		// 存证最晚在超时前一个区块生效，生效时过期区块必须还没到
+		if let Some(expires_at) = expires_at {
+			ensure!(expires_at >= timeout_at, Error::<T>::InvalidExpiry);
+		}
+
+		let responses = cosigners.len();
+		let mut pending = PendingClaim::<T> {
+			owner: sender.clone(),
+			metadata,
+			expires_at,
+			cosigners,
+			threshold,
+			approvals: Default::default(),
+			rejections: Default::default(),
+			timeout_at,
+			deposit: Zero::zero(),
+		};
+		// 每个共同签名人签名或者拒绝时都会增加一个账户，按所有人都回应之后的大小质押，
+		// 再加上生效时创建的历史记录
+		let len = pending.encoded_size() +
+			responses * T::AccountId::max_encoded_len() +
+			CustodyRecordOf::<T>::max_encoded_len();
+		pending.deposit = Self::deposit_for_len(len);
+		ensure!(
+			T::Currency::can_reserve(&sender, pending.deposit),
+			Error::<T>::InsufficientBalance
+		);
+		PendingTimeoutsAt::<T>::try_mutate(timeout_at, |hashes| hashes.try_push(claim_hash))
+			.map_err(|_| Error::<T>::TooManyPending)?;
+		T::Currency::reserve(&sender, pending.deposit)?;
+		PendingClaims::<T>::insert(&claim_hash, pending);
+
+		let event = Event::PendingClaimCreated(sender, claim_hash, threshold, timeout_at);
+		Self::deposit_event(event);
+		Ok(())
+	}
+
+	pub(crate) fn do_cosign_claim(signer: T::AccountId, claim_hash: ContentHash) -> DispatchResult {
+		let mut pending = Self::pending_for_response(&signer, &claim_hash)?;
+		pending.approvals.try_push(signer.clone()).map_err(|_| Error::<T>::AlreadyCosigned)?;
+
+		if (pending.approvals.len() as u32) < pending.threshold {
+			PendingClaims::<T>::insert(&claim_hash, pending);
+			Self::deposit_event(Event::ClaimCosigned(signer, claim_hash));
+			return Ok(())
+		}
+
+		// 签名的人数够了，存证生效
+		Self::reschedule_expiry(claim_hash, None, pending.expires_at)?;
+		let created_at = T::Time::now();
+		let proof = Proof {
+			owner: pending.owner.clone(),
+			block_number: frame_system::Pallet::<T>::block_number(),
+			created_at,
+			metadata: pending.metadata,
+			expires_at: pending.expires_at,
+		};
+		let record = Self::created_record(&proof);
+		PendingClaims::<T>::remove(&claim_hash);
+		Self::unbury(&claim_hash);
+		Proofs::<T>::insert(&claim_hash, proof);
+		ClaimDeposits::<T>::insert(&claim_hash, pending.deposit);
+		Self::start_history(&claim_hash, record);
+
+		Self::deposit_event(Event::ClaimCosigned(signer, claim_hash));
+		Self::deposit_event(Event::ClaimFinalized(pending.owner, claim_hash, created_at));
+		Ok(())
+	}
+
+	pub(crate) fn do_reject_claim(signer: T::AccountId, claim_hash: ContentHash) -> DispatchResult {
+		let mut pending = Self::pending_for_response(&signer, &claim_hash)?;
+		pending.rejections.try_push(signer.clone()).map_err(|_| Error::<T>::AlreadyCosigned)?;
+		Self::deposit_event(Event::ClaimRejected(signer, claim_hash));
+
+		// 剩下的人全部签名也达不到threshold时删除
+		let remaining = pending.cosigners.len().saturating_sub(pending.rejections.len());
+		if (remaining as u32) < pending.threshold {
+			PendingClaims::<T>::remove(&claim_hash);
+			T::Currency::unreserve(&pending.owner, pending.deposit);
+			Self::deposit_event(Event::PendingClaimRejected(pending.owner, claim_hash));
+		} else {
+			PendingClaims::<T>::insert(&claim_hash, pending);
+		}
+		Ok(())
+	}
+
+	/// 校验signer可以对这个待签名的存证签名或者拒绝
+	fn pending_for_response(
+		signer: &T::AccountId,
+		claim_hash: &ContentHash,
+	) -> Result<PendingClaim<T>, DispatchError> {
+		let pending = PendingClaims::<T>::get(claim_hash).ok_or(Error::<T>::PendingClaimNotExist)?;
+		ensure!(pending.cosigners.contains(signer), Error::<T>::NotCosigner);
+		ensure!(
+			!pending.approvals.contains(signer) && !pending.rejections.contains(signer),
+			Error::<T>::AlreadyCosigned
+		);
+		Ok(pending)
+	}
+
+	/// 删除在now超时的待签名存证，返回列表中存证的数量
+	pub(crate) fn time_out_pending_claims(now: T::BlockNumber) -> u32 {
+		let hashes = PendingTimeoutsAt::<T>::take(now);
+		for claim_hash in hashes.iter() {
+			// 已经生效或者被拒绝的存证不在PendingClaims中，摘要可能又被重新创建，
+			// 所以还要检查超时区块
+			let timed_out = PendingClaims::<T>::get(claim_hash)
+				.filter(|pending| pending.timeout_at == now);
+			if let Some(pending) = timed_out {
+				PendingClaims::<T>::remove(claim_hash);
+				T::Currency::unreserve(&pending.owner, pending.deposit);
+				Self::deposit_event(Event::PendingClaimTimedOut(pending.owner, *claim_hash));
+			}
+		}
+		hashes.len() as u32
+	}
+}
@@ -0,0 +1,111 @@
+//! 存证的过期和清理
+//!
+//! 设置了过期区块的存证记录在`ClaimsExpiringAt`中，`NextExpiryBlock`之前的区块都已经清理完。
+//! 每个区块在on_initialize中清理`ExpiryBatchSize`个，区块有剩余空间时在on_idle中继续清理，
+//! 所以过期的存证可能会晚几个区块才被删除，在这之前存证不能再被操作。
+use crate::*;
+use frame_support::{pallet_prelude::*, weights::Weight};
+use sp_runtime::traits::{One, Saturating};
+
+impl<T: Config> Pallet<T> {
+	pub(crate) fn is_expired(proof: &ProofOf<T>) -> bool {
+		let now = frame_system::Pallet::<T>::block_number();
+		proof.expires_at.map_or(false, |expires_at| expires_at <= now)
+	}
+
+	/// 把存证的过期区块从old改成new，失败时不会修改存储
+	pub(crate) fn reschedule_expiry(
+		claim_hash: ContentHash,
+		old: Option<T::BlockNumber>,
+		new: Option<T::BlockNumber>,
+	) -> DispatchResult {
+		if let Some(new) = new {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(new > now, Error::<T>::InvalidExpiry);
+		}
+		if old == new {
+			return Ok(())
+		}
+
+		if let Some(new) = new {
+			ClaimsExpiringAt::<T>::try_mutate(new, |hashes| hashes.try_push(claim_hash))
+				.map_err(|_| Error::<T>::TooManyExpiring)?;
+		}
+		if let Some(old) = old {
+			Self::remove_expiry_schedule(&claim_hash, old);
+		}
+		Ok(())
+	}
+
+	/// 将存证从过期区块的待清理列表中移除
+	pub(crate) fn remove_expiry_schedule(claim_hash: &ContentHash, expires_at: T::BlockNumber) {
+		ClaimsExpiringAt::<T>::mutate_exists(expires_at, |maybe_hashes| {
+			if let Some(hashes) = maybe_hashes {
+				hashes.retain(|hash| hash != claim_hash);
+				// 列表空了就删除，清理时不需要再写入
+				if hashes.is_empty() {
+					*maybe_hashes = None;
+				}
+			}
+		});
+	}
+
+	/// 清理到now为止过期的存证，返回处理的数量，读取一个区块的列表和删除一个存证都算一次，
+	/// 最多处理limit次
+	pub(crate) fn sweep_expired(now: T::BlockNumber, limit: u32) -> u32 {
+		let mut block = NextExpiryBlock::<T>::get();
+		let mut swept = 0u32;
+		while block <= now && swept < limit {
+			let mut expiring = ClaimsExpiringAt::<T>::get(block);
+			let scheduled = !expiring.is_empty();
+			swept += 1;
+
+			// 一个区块的存证可能要分几次才能清理完
+			while swept < limit {
+				match expiring.pop() {
+					Some(claim_hash) => {
+						Self::expire_claim(claim_hash, block);
+						swept += 1;
+					},
+					None => break,
+				}
+			}
+			if !expiring.is_empty() {
+				ClaimsExpiringAt::<T>::insert(block, expiring);
+				break
+			}
+
+			if scheduled {
+				ClaimsExpiringAt::<T>::remove(block);
+			}
+			block = block.saturating_add(One::one());
+		}
+		NextExpiryBlock::<T>::put(block);
+		swept
+	}
+
+	/// remaining_weight内最多可以清理多少次
+	pub(crate) fn sweep_limit(remaining_weight: Weight) -> u32 {
+		let base = T::WeightInfo::expire_claims(0);
+		let per_item = T::WeightInfo::expire_claims(1).saturating_sub(base);
+		remaining_weight
+			.checked_sub(&base)
+			.and_then(|remaining| remaining.checked_div_per_component(&per_item))
+			.map_or(0, |limit| limit.min(u32::MAX as u64) as u32)
+	}
+
+	fn expire_claim(claim_hash: ContentHash, block: T::BlockNumber) {
+		// 列表中的存证一定在这个区块过期，续期和撤销时都会修改列表，这里再检查一次
+		if let Some(proof) = Proofs::<T>::get(&claim_hash) {
+			if proof.expires_at == Some(block) {
+				let tombstone = Tombstone {
+					owner: proof.owner.clone(),
+					block_number: block,
+					cause: TombstoneCause::Expired,
+				};
+				Self::bury(claim_hash, tombstone);
+				Self::deposit_event(Event::ClaimExpired(proof.owner, claim_hash));
+			}
+		}
+	}
+}
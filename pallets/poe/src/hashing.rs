@@ -0,0 +1,50 @@
+//! 存证的内容哈希。链上只保存固定长度的摘要和计算它的哈希算法，文件内容留在链下。
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+
+/// 计算存证摘要使用的哈希算法
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum HashAlgorithm {
+	/// `create_claim`在链上计算哈希时使用的算法
+	#[default]
+	Blake2_256,
+	Sha2_256,
+	Keccak256,
+}
+
+/// 带算法标记的内容摘要，用作存证的key
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContentHash {
+	pub algorithm: HashAlgorithm,
+	pub digest: [u8; 32],
+}
+
+impl ContentHash {
+	pub fn new(algorithm: HashAlgorithm, digest: [u8; 32]) -> Self {
+		ContentHash { algorithm, digest }
+	}
+
+	/// 用algorithm计算content的摘要，链上和链下得到的结果相同
+	pub fn of(algorithm: HashAlgorithm, content: &[u8]) -> Self {
+		let digest = match algorithm {
+			HashAlgorithm::Blake2_256 => sp_io::hashing::blake2_256(content),
+			HashAlgorithm::Sha2_256 => sp_io::hashing::sha2_256(content),
+			HashAlgorithm::Keccak256 => sp_io::hashing::keccak_256(content),
+		};
+		ContentHash { algorithm, digest }
+	}
+}
+
+/// 在链下计算文件的摘要，结果可以直接提交给`create_claim_for_hash`
+#[cfg(feature = "std")]
+pub fn hash_file(
+	algorithm: HashAlgorithm,
+	path: impl AsRef<std::path::Path>,
+) -> std::io::Result<ContentHash> {
+	std::fs::read(path).map(|content| ContentHash::of(algorithm, &content))
+}
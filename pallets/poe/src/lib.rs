@@ -2,6 +2,21 @@
 
 pub use pallet::*;
 
+mod cosign;
+mod expiry;
+pub mod hashing;
+pub mod merkle;
+pub mod migrations;
+mod query;
+mod tombstone;
+pub mod weights;
+pub use hashing::{ContentHash, HashAlgorithm};
+pub use merkle::MerkleProof;
+pub use weights::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[cfg(test)]
 mod mock;
 
@@ -11,34 +26,355 @@ mod tests;
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{BalanceStatus, Currency, ReservableCurrency, Time},
+		CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	};
+	use sp_core::H256;
+	use sp_runtime::traits::{Saturating, Zero};
 	use frame_system::{ensure_signed, pallet_prelude::*};
 	use sp_std::prelude::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
+	/// 存证的描述信息，长度都由Config限制
+	#[derive(
+		CloneNoBound,
+		DefaultNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		RuntimeDebugNoBound,
+		Encode,
+		Decode,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(DescriptionLimit, MimeTypeLimit, UriLimit))]
+	#[codec(mel_bound())]
+	pub struct ClaimMetadata<DescriptionLimit, MimeTypeLimit, UriLimit>
+	where
+		DescriptionLimit: Get<u32>,
+		MimeTypeLimit: Get<u32>,
+		UriLimit: Get<u32>,
+	{
+		pub description: BoundedVec<u8, DescriptionLimit>,
+		/// 例如`application/pdf`
+		pub mime_type: BoundedVec<u8, MimeTypeLimit>,
+		/// 文件在链下保存的位置
+		pub uri: Option<BoundedVec<u8, UriLimit>>,
+	}
+
+	/// 链上保存的存证
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Proof<AccountId, BlockNumber, Moment, Metadata> {
+		pub owner: AccountId,
+		/// 创建或者转移时的区块
+		pub block_number: BlockNumber,
+		/// 创建时的时间戳，从v1迁移过来的存证没有记录创建时间，为0
+		pub created_at: Moment,
+		pub metadata: Metadata,
+		/// 在这个区块开始时失效，None表示永久有效
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	/// 存证所有权的变化
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum CustodyAction {
+		/// 创建存证，多方签名的存证在生效时创建
+		Created,
+		Transferred,
+	}
+
+	/// 存证历史中的一条记录
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub struct CustodyRecord<AccountId, BlockNumber> {
+		/// 这次变化之后的owner
+		pub owner: AccountId,
+		pub block_number: BlockNumber,
+		pub action: CustodyAction,
+	}
+
+	pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	pub type ClaimMetadataOf<T> = ClaimMetadata<
+		<T as Config>::MaxDescriptionLength,
+		<T as Config>::MaxMimeTypeLength,
+		<T as Config>::MaxUriLength,
+	>;
+
+	pub type CustodyRecordOf<T> = CustodyRecord<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	pub type ProofOf<T> = Proof<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		MomentOf<T>,
+		ClaimMetadataOf<T>,
+	>;
+
+	/// 存证失效的原因
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum TombstoneCause<Reason> {
+		/// owner主动撤销，code和reason由owner填写
+		Revoked { code: u16, reason: Reason },
+		Expired,
+	}
+
+	/// 存证被撤销或者过期后留下的记录，用来区分存证失效和从来没有存在过
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Tombstone<AccountId, BlockNumber, Reason> {
+		/// 失效时的owner，撤销的存证就是撤销的人
+		pub owner: AccountId,
+		/// 撤销或者过期的区块
+		pub block_number: BlockNumber,
+		pub cause: TombstoneCause<Reason>,
+	}
+
+	pub type TombstoneOf<T> = Tombstone<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BoundedVec<u8, <T as Config>::MaxReasonLength>,
+	>;
+
+	/// 摘要对应的存证现在的状态
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+	pub enum ClaimStatus {
+		Active,
+		/// 等待共同签名
+		Pending,
+		Revoked,
+		Expired,
+	}
+
+	/// 一批文件的存证，只保存所有文件摘要构成的Merkle树的根
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Anchor<AccountId, Balance, BlockNumber, Moment, Metadata> {
+		pub owner: AccountId,
+		/// 批次中文件的数量，校验证明时需要
+		pub leaf_count: u32,
+		pub block_number: BlockNumber,
+		pub created_at: Moment,
+		pub metadata: Metadata,
+		/// 在owner账户上质押的token数量
+		pub deposit: Balance,
+	}
+
+	pub type AnchorOf<T> = Anchor<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		MomentOf<T>,
+		ClaimMetadataOf<T>,
+	>;
+
+	/// 等待共同签名的存证，签名的人数达到threshold之后写入`Proofs`
+	#[derive(
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		RuntimeDebugNoBound,
+		Encode,
+		Decode,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct PendingClaim<T: Config> {
+		pub owner: T::AccountId,
+		pub metadata: ClaimMetadataOf<T>,
+		/// 存证生效后的过期区块
+		pub expires_at: Option<T::BlockNumber>,
+		/// 需要签名的人，不能重复
+		pub cosigners: BoundedVec<T::AccountId, T::MaxCosigners>,
+		/// 至少需要多少个人签名
+		pub threshold: u32,
+		/// 已经签名的人
+		pub approvals: BoundedVec<T::AccountId, T::MaxCosigners>,
+		/// 拒绝签名的人
+		pub rejections: BoundedVec<T::AccountId, T::MaxCosigners>,
+		/// 在这个区块开始时超时
+		pub timeout_at: T::BlockNumber,
+		/// 在owner账户上质押的token数量，存证生效后作为存证的质押
+		pub deposit: BalanceOf<T>,
+	}
+
 	#[pallet::config] // 模块配置
 	pub trait Config: frame_system::Config {
 		#[pallet::constant]
 		type MaxClaimLength: Get<u32>;
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// 记录存证创建时间的时钟，一般是pallet_timestamp
+		type Time: Time;
+
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// 每个存证都要质押的token数量
+		#[pallet::constant]
+		type ClaimDepositBase: Get<BalanceOf<Self>>;
+
+		/// 存证每占用一个字节的存储需要额外质押的token数量
+		#[pallet::constant]
+		type ClaimDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// 存证描述的最大长度
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+
+		/// 存证MIME类型的最大长度
+		#[pallet::constant]
+		type MaxMimeTypeLength: Get<u32>;
+
+		/// 存证URI的最大长度
+		#[pallet::constant]
+		type MaxUriLength: Get<u32>;
+
+		/// 同一个区块内最多有多少个存证过期
+		#[pallet::constant]
+		type MaxExpiringPerBlock: Get<u32>;
+
+		/// on_initialize中每个区块最多清理多少个过期存证，剩下的在on_idle中清理
+		#[pallet::constant]
+		type ExpiryBatchSize: Get<u32>;
+
+		/// 一个存证最多可以有多少个共同签名人
+		#[pallet::constant]
+		type MaxCosigners: Get<u32>;
+
+		/// 待签名的存证在多少个区块内没有完成签名就作废
+		#[pallet::constant]
+		type PendingClaimTimeout: Get<Self::BlockNumber>;
+
+		/// 同一个区块内最多创建多少个待签名的存证，它们会在同一个区块超时
+		#[pallet::constant]
+		type MaxPendingPerBlock: Get<u32>;
+
+		/// 每个存证最多保存多少条历史记录，包括创建的那一条
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+
+		/// 撤销原因的最大长度
+		#[pallet::constant]
+		type MaxReasonLength: Get<u32>;
+
+		/// 是否允许重新创建已经撤销或者过期的存证，允许时重新创建会删除原来的记录
+		#[pallet::constant]
+		type AllowReclaim: Get<bool>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	// 存证的内容摘要 => 存证
+	#[pallet::storage]
+	#[pallet::getter(fn proofs)]
+	pub(super) type Proofs<T: Config> = StorageMap<_, Blake2_128Concat, ContentHash, ProofOf<T>>;
+
+	/// 每个存证在owner账户上质押的token数量，转移存证时随之转移。撤销或者过期时只保留失效的
+	/// 记录需要的部分，失效的记录被删除时退回。引入质押之前创建的存证没有质押
+	#[pallet::storage]
+	#[pallet::getter(fn claim_deposit)]
+	pub type ClaimDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContentHash, BalanceOf<T>, ValueQuery>;
+
+	/// 存证的所有权历史，只会追加，存证被撤销或者过期后继续保留，取回质押时也不会删除。
+	/// 只有`AllowReclaim`允许重新创建存证时才会被新的历史替换。
+	/// 引入历史记录之前创建的存证没有创建记录
 	#[pallet::storage]
-	pub(super) type Proofs<T: Config> = StorageMap<
+	pub type ClaimHistory<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
-		BoundedVec<u8, T::MaxClaimLength>,
-		(T::AccountId, T::BlockNumber),
+		ContentHash,
+		BoundedVec<CustodyRecordOf<T>, T::MaxHistoryLength>,
+		ValueQuery,
+	>;
+
+	/// 撤销或者过期的存证，由失效时的owner质押
+	#[pallet::storage]
+	#[pallet::getter(fn tombstones)]
+	pub type Tombstones<T: Config> = StorageMap<_, Blake2_128Concat, ContentHash, TombstoneOf<T>>;
+
+	/// Merkle根 => 批量存证
+	#[pallet::storage]
+	#[pallet::getter(fn anchors)]
+	pub type Anchors<T: Config> = StorageMap<_, Blake2_128Concat, H256, AnchorOf<T>>;
+
+	/// 每个区块过期的存证
+	#[pallet::storage]
+	pub type ClaimsExpiringAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<ContentHash, T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	/// 下一个需要清理过期存证的区块，之前的区块都已经清理完
+	#[pallet::storage]
+	pub type NextExpiryBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// 摘要 => 等待共同签名的存证
+	#[pallet::storage]
+	#[pallet::getter(fn pending_claims)]
+	pub type PendingClaims<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContentHash, PendingClaim<T>>;
+
+	/// 每个区块超时的待签名存证
+	#[pallet::storage]
+	pub type PendingTimeoutsAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<ContentHash, T::MaxPendingPerBlock>,
+		ValueQuery,
 	>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		ClaimCreated(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
-		ClaimRevoked(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// (owner, 摘要, 描述信息, 创建时间)
+		ClaimCreated(T::AccountId, ContentHash, ClaimMetadataOf<T>, MomentOf<T>),
+		/// (owner, 摘要, 撤销原因代码)
+		ClaimRevoked(T::AccountId, ContentHash, u16),
+		/// (owner, 摘要, 新的过期区块)
+		ClaimRenewed(T::AccountId, ContentHash, Option<T::BlockNumber>),
+		/// 存证到期被清理: (owner, 摘要)
+		ClaimExpired(T::AccountId, ContentHash),
+		/// (owner, Merkle根, 文件数量, 描述信息, 创建时间)
+		BatchAnchored(T::AccountId, H256, u32, ClaimMetadataOf<T>, MomentOf<T>),
+		/// (owner, 摘要, 需要的签名数, 超时区块)
+		PendingClaimCreated(T::AccountId, ContentHash, u32, T::BlockNumber),
+		/// (共同签名人, 摘要)
+		ClaimCosigned(T::AccountId, ContentHash),
+		/// (共同签名人, 摘要)
+		ClaimRejected(T::AccountId, ContentHash),
+		/// 签名的人数达到要求，存证生效: (owner, 摘要, 生效时间)
+		ClaimFinalized(T::AccountId, ContentHash, MomentOf<T>),
+		/// 拒绝的人太多，不可能再达到要求: (owner, 摘要)
+		PendingClaimRejected(T::AccountId, ContentHash),
+		/// 没有在规定的区块内完成签名: (owner, 摘要)
+		PendingClaimTimedOut(T::AccountId, ContentHash),
+		/// (原来的owner, 新的owner, 摘要)
+		ClaimTransferred(T::AccountId, T::AccountId, ContentHash),
+		/// 批量存证被删除，质押已经退回: (owner, Merkle根)
+		AnchorRemoved(T::AccountId, H256),
+		/// 失效的记录的质押已经退回，撤销原因被删除: (owner, 摘要)
+		TombstoneDepositReleased(T::AccountId, ContentHash),
 	}
 
 	#[pallet::error]
@@ -47,74 +383,383 @@ pub mod pallet {
 		ClaimTooLong,
 		ClaimNotExist,
 		NotClaimOwner,
+		/// 过期区块必须在当前区块之后
+		InvalidExpiry,
+		/// 这个区块过期的存证太多了
+		TooManyExpiring,
+		/// 存证已经过期，等待清理
+		ClaimExpired,
+		/// 余额不足以支付存证的质押
+		InsufficientBalance,
+		/// 批次中至少要有一个文件
+		EmptyBatch,
+		/// 这个Merkle根已经存证过了
+		AnchorAlreadyExist,
+		/// threshold必须大于0并且不能超过共同签名人的数量
+		InvalidThreshold,
+		/// 共同签名人不能重复
+		DuplicateCosigner,
+		PendingClaimNotExist,
+		/// 不在这个存证的共同签名人中
+		NotCosigner,
+		/// 已经签名或者拒绝过了
+		AlreadyCosigned,
+		/// 这个区块创建的待签名存证太多了
+		TooManyPending,
+		/// 存证的历史记录已经满了，不能再转移
+		HistoryFull,
+		/// 存证已经被撤销或者过期，不允许重新创建
+		ClaimTombstoned,
+		AnchorNotExist,
+		TombstoneNotExist,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			// 每个区块先清理一小批，保证过期的存证尽快被删除
+			let swept = Self::sweep_expired(n, T::ExpiryBatchSize::get());
+			let timed_out = Self::time_out_pending_claims(n);
+			T::WeightInfo::expire_claims(swept)
+				.saturating_add(T::WeightInfo::time_out_pending_claims(timed_out))
+		}
+
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			// 区块还有剩余空间时继续清理积压的过期存证
+			let limit = Self::sweep_limit(remaining_weight);
+			if limit == 0 {
+				return Weight::zero()
+			}
+			let swept = Self::sweep_expired(n, limit);
+			T::WeightInfo::expire_claims(swept)
+		}
+
+		fn integrity_test() {
+			// 读取一个区块的列表也算一次，至少为2才能保证每个区块都有进展
+			assert!(T::ExpiryBatchSize::get() > 1, "ExpiryBatchSize must be greater than 1");
+			assert!(T::MaxHistoryLength::get() > 0, "MaxHistoryLength must be greater than 0");
+			// 超时区块的on_initialize已经执行过就不会再清理
+			assert!(
+				!T::PendingClaimTimeout::get().is_zero(),
+				"PendingClaimTimeout must be greater than 0"
+			);
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		// 创建存证
-		#[pallet::weight(0)]
+		// 创建存证，链上用默认算法计算claim的摘要，只保存摘要
+		#[pallet::weight(T::WeightInfo::create_claim(claim.len() as u32))]
 		#[pallet::call_index(0)]
 		pub fn create_claim(
 			origin: OriginFor<T>,
 			claim: BoundedVec<u8, T::MaxClaimLength>,
+			metadata: ClaimMetadataOf<T>,
+			expires_at: Option<T::BlockNumber>,
 		) -> DispatchResult {
 			// origin 交易的发送方，claim 存证
 			// 校验交易的发送方
 			let sender = ensure_signed(origin)?;
-			// 校验存证是否超过我们允许的最大长度
-			// 将claim转换成BoundedVec类型
-			// let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim.clone()).map_err(|_| Error::<T>::ClaimTooLong)?;
-
-			// 校验要创建的存证现在还不存在，如果存在则返回ProofAlreadyExist错误
-			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
-
-			// 插入存证
-			Proofs::<T>::insert(
-				&claim,
-				(sender.clone(), frame_system::Pallet::<T>::block_number()),
-			);
-
-			// 触发事件: 存证被创建
-			Self::deposit_event(Event::ClaimCreated(sender, claim));
-
-			Ok(())
+			let claim_hash = ContentHash::of(HashAlgorithm::default(), &claim);
+			Self::do_create_claim(sender, claim_hash, metadata, expires_at)
 		}
 
-		// 销毁存证
-		#[pallet::weight(1)]
+		// 销毁存证，链上留下撤销的记录
+		#[pallet::weight(T::WeightInfo::revoke_claim())]
+		#[pallet::call_index(1)]
 		pub fn revoke_claim(
 			origin: OriginFor<T>,
-			claim: BoundedVec<u8, T::MaxClaimLength>,
+			claim_hash: ContentHash,
+			code: u16,
+			reason: BoundedVec<u8, T::MaxReasonLength>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			// let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim.clone()).map_err(|_| Error::<T>::ClaimTooLong)?;
-			let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?; // 获取到这个claim的owner
+			// 获取到这个claim的owner
+			let proof = Proofs::<T>::get(&claim_hash).ok_or(Error::<T>::ClaimNotExist)?;
 
 			// 校验交易的发送方和claim的owner一致
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(proof.owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(!Self::is_expired(&proof), Error::<T>::ClaimExpired);
+
+			let tombstone = Tombstone {
+				owner: sender.clone(),
+				block_number: frame_system::Pallet::<T>::block_number(),
+				cause: TombstoneCause::Revoked { code, reason },
+			};
+			// 撤销原因比存证长时需要补足失效的记录的质押
+			let extra = Self::tombstone_deposit(&claim_hash, &tombstone)
+				.saturating_sub(Self::claim_deposit(&claim_hash));
+			ensure!(T::Currency::can_reserve(&sender, extra), Error::<T>::InsufficientBalance);
 
-			Proofs::<T>::remove(&claim);
-			Self::deposit_event(Event::ClaimRevoked(sender, claim));
+			if let Some(expires_at) = proof.expires_at {
+				Self::remove_expiry_schedule(&claim_hash, expires_at);
+			}
+			Self::bury(claim_hash, tombstone);
+			Self::deposit_event(Event::ClaimRevoked(sender, claim_hash, code));
 			Ok(())
 		}
 
 		// 转移存证
-		#[pallet::weight(2)]
+		#[pallet::weight(T::WeightInfo::transfer_claim())]
+		#[pallet::call_index(2)]
 		pub fn transfer_claim(
 			origin: OriginFor<T>,
-			claim: BoundedVec<u8, T::MaxClaimLength>,
+			claim_hash: ContentHash,
 			dest: T::AccountId,
 		) -> DispatchResult {
 			// 验证发送方
 			let sender = ensure_signed(origin)?;
 
-			// let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim.clone()).map_err(|_| Error::<T>::ClaimTooLong)?;
+			let mut proof = Proofs::<T>::get(&claim_hash).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(proof.owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(!Self::is_expired(&proof), Error::<T>::ClaimExpired);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let record = CustodyRecord {
+				owner: dest.clone(),
+				block_number: now,
+				action: CustodyAction::Transferred,
+			};
+			// 新增的历史记录由原来的owner质押
+			let history_deposit = Self::history_deposit(&record);
+			ensure!(
+				T::Currency::can_reserve(&sender, history_deposit),
+				Error::<T>::InsufficientBalance
+			);
+			let mut history = ClaimHistory::<T>::get(&claim_hash);
+			history.try_push(record).map_err(|_| Error::<T>::HistoryFull)?;
+			T::Currency::reserve(&sender, history_deposit)?;
 
-			let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			// 质押由新的owner承担
+			let deposit = Self::claim_deposit(&claim_hash).saturating_add(history_deposit);
+			if !deposit.is_zero() {
+				T::Currency::repatriate_reserved(&sender, &dest, deposit, BalanceStatus::Reserved)?;
+			}
+			ClaimDeposits::<T>::insert(&claim_hash, deposit);
+			ClaimHistory::<T>::insert(&claim_hash, history);
 
-			Proofs::<T>::insert(claim, (dest, frame_system::Pallet::<T>::block_number()));
+			// 描述信息、创建时间和过期区块不变
+			proof.owner = dest.clone();
+			proof.block_number = now;
+			Proofs::<T>::insert(&claim_hash, proof);
+
+			Self::deposit_event(Event::ClaimTransferred(sender, dest, claim_hash));
 			Ok(().into())
 		}
+
+		// 用链下计算好的摘要创建存证，可以选择任意支持的哈希算法
+		#[pallet::weight(T::WeightInfo::create_claim_for_hash())]
+		#[pallet::call_index(3)]
+		pub fn create_claim_for_hash(
+			origin: OriginFor<T>,
+			claim_hash: ContentHash,
+			metadata: ClaimMetadataOf<T>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_claim(sender, claim_hash, metadata, expires_at)
+		}
+
+		// 修改存证的过期区块，None表示永久有效
+		#[pallet::weight(T::WeightInfo::renew_claim())]
+		#[pallet::call_index(4)]
+		pub fn renew_claim(
+			origin: OriginFor<T>,
+			claim_hash: ContentHash,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut proof = Proofs::<T>::get(&claim_hash).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(proof.owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(!Self::is_expired(&proof), Error::<T>::ClaimExpired);
+
+			Self::reschedule_expiry(claim_hash, proof.expires_at, expires_at)?;
+			proof.expires_at = expires_at;
+			Proofs::<T>::insert(&claim_hash, proof);
+
+			Self::deposit_event(Event::ClaimRenewed(sender, claim_hash, expires_at));
+			Ok(())
+		}
+
+		// 批量存证，root由链下的`merkle::MerkleTree`计算，之后可以用`verify_inclusion`
+		// 校验某个文件是否在这个批次中
+		#[pallet::weight(T::WeightInfo::anchor_batch())]
+		#[pallet::call_index(5)]
+		pub fn anchor_batch(
+			origin: OriginFor<T>,
+			root: H256,
+			leaf_count: u32,
+			metadata: ClaimMetadataOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(leaf_count > 0, Error::<T>::EmptyBatch);
+			ensure!(!Anchors::<T>::contains_key(root), Error::<T>::AnchorAlreadyExist);
+
+			let created_at = T::Time::now();
+			let mut anchor = Anchor {
+				owner: sender.clone(),
+				leaf_count,
+				block_number: frame_system::Pallet::<T>::block_number(),
+				created_at,
+				metadata: metadata.clone(),
+				deposit: Zero::zero(),
+			};
+			anchor.deposit = Self::deposit_for_len(anchor.encoded_size());
+			ensure!(
+				T::Currency::can_reserve(&sender, anchor.deposit),
+				Error::<T>::InsufficientBalance
+			);
+			T::Currency::reserve(&sender, anchor.deposit)?;
+			Anchors::<T>::insert(root, anchor);
+
+			let event = Event::BatchAnchored(sender, root, leaf_count, metadata, created_at);
+			Self::deposit_event(event);
+			Ok(())
+		}
+
+		// 创建需要多方签名的存证，cosigners中至少threshold个人签名之后存证才生效，
+		// PendingClaimTimeout个区块内没有完成签名则作废
+		#[pallet::weight(T::WeightInfo::create_pending_claim(cosigners.len() as u32))]
+		#[pallet::call_index(6)]
+		pub fn create_pending_claim(
+			origin: OriginFor<T>,
+			claim_hash: ContentHash,
+			metadata: ClaimMetadataOf<T>,
+			expires_at: Option<T::BlockNumber>,
+			cosigners: BoundedVec<T::AccountId, T::MaxCosigners>,
+			threshold: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_pending_claim(
+				sender,
+				claim_hash,
+				metadata,
+				expires_at,
+				cosigners,
+				threshold,
+			)
+		}
+
+		// 共同签名人签名，最后一个需要的签名使存证生效
+		#[pallet::weight(T::WeightInfo::cosign_claim(T::MaxCosigners::get()))]
+		#[pallet::call_index(7)]
+		pub fn cosign_claim(origin: OriginFor<T>, claim_hash: ContentHash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_cosign_claim(sender, claim_hash)
+		}
+
+		// 共同签名人拒绝签名
+		#[pallet::weight(T::WeightInfo::reject_claim(T::MaxCosigners::get()))]
+		#[pallet::call_index(8)]
+		pub fn reject_claim(origin: OriginFor<T>, claim_hash: ContentHash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_reject_claim(sender, claim_hash)
+		}
+
+		// 删除批量存证并退回质押，只有owner可以删除
+		#[pallet::weight(T::WeightInfo::remove_anchor())]
+		#[pallet::call_index(9)]
+		pub fn remove_anchor(origin: OriginFor<T>, root: H256) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let anchor = Anchors::<T>::get(root).ok_or(Error::<T>::AnchorNotExist)?;
+			ensure!(anchor.owner == sender, Error::<T>::NotClaimOwner);
+
+			Anchors::<T>::remove(root);
+			T::Currency::unreserve(&sender, anchor.deposit);
+			Self::deposit_event(Event::AnchorRemoved(sender, root));
+			Ok(())
+		}
+
+		// 退回撤销或者过期的存证留下的记录的质押，只有失效时的owner可以操作。
+		// 只删除撤销原因，失效的记录和历史记录仍然保留
+		#[pallet::weight(T::WeightInfo::release_tombstone_deposit())]
+		#[pallet::call_index(10)]
+		pub fn release_tombstone_deposit(
+			origin: OriginFor<T>,
+			claim_hash: ContentHash,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut tombstone =
+				Tombstones::<T>::get(&claim_hash).ok_or(Error::<T>::TombstoneNotExist)?;
+			ensure!(tombstone.owner == sender, Error::<T>::NotClaimOwner);
+
+			if let TombstoneCause::Revoked { reason, .. } = &mut tombstone.cause {
+				*reason = Default::default();
+			}
+			Tombstones::<T>::insert(&claim_hash, tombstone);
+			T::Currency::unreserve(&sender, ClaimDeposits::<T>::take(&claim_hash));
+			Self::deposit_event(Event::TombstoneDepositReleased(sender, claim_hash));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn do_create_claim(
+			sender: T::AccountId,
+			claim_hash: ContentHash,
+			metadata: ClaimMetadataOf<T>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			// 校验要创建的存证现在还不存在，如果存在则返回ProofAlreadyExist错误
+			Self::ensure_claimable(&claim_hash)?;
+
+			let created_at = T::Time::now();
+			let proof = Proof {
+				owner: sender.clone(),
+				block_number: frame_system::Pallet::<T>::block_number(),
+				created_at,
+				metadata: metadata.clone(),
+				expires_at,
+			};
+			let record = Self::created_record(&proof);
+			let deposit = Self::deposit_for(&proof).saturating_add(Self::history_deposit(&record));
+			ensure!(T::Currency::can_reserve(&sender, deposit), Error::<T>::InsufficientBalance);
+			Self::reschedule_expiry(claim_hash, None, expires_at)?;
+			T::Currency::reserve(&sender, deposit)?; // 质押deposit数量的token
+
+			// 插入存证
+			// 重新创建时删除原来的记录
+			Self::unbury(&claim_hash);
+			Proofs::<T>::insert(&claim_hash, proof);
+			ClaimDeposits::<T>::insert(&claim_hash, deposit);
+			Self::start_history(&claim_hash, record);
+
+			// 触发事件: 存证被创建
+			Self::deposit_event(Event::ClaimCreated(sender, claim_hash, metadata, created_at));
+
+			Ok(())
+		}
+
+		/// 存证需要的质押: 基础质押加上存证编码后的字节数乘以每字节的质押
+		pub fn deposit_for(proof: &ProofOf<T>) -> BalanceOf<T> {
+			Self::deposit_for_len(proof.encoded_size())
+		}
+
+		/// 一条历史记录需要的质押，不包括基础质押
+		pub(crate) fn history_deposit(record: &CustodyRecordOf<T>) -> BalanceOf<T> {
+			let bytes = BalanceOf::<T>::from(record.encoded_size() as u32);
+			T::ClaimDepositPerByte::get().saturating_mul(bytes)
+		}
+
+		pub(crate) fn created_record(proof: &ProofOf<T>) -> CustodyRecordOf<T> {
+			CustodyRecord {
+				owner: proof.owner.clone(),
+				block_number: proof.block_number,
+				action: CustodyAction::Created,
+			}
+		}
+
+		/// 新的存证的历史记录，只有创建这一条
+		pub(crate) fn start_history(claim_hash: &ContentHash, record: CustodyRecordOf<T>) {
+			let history = BoundedVec::try_from(sp_std::vec![record])
+				.expect("MaxHistoryLength is at least 1; qed");
+			ClaimHistory::<T>::insert(claim_hash, history);
+		}
+
+		pub(crate) fn deposit_for_len(len: usize) -> BalanceOf<T> {
+			let bytes = BalanceOf::<T>::from(len as u32);
+			let per_byte = T::ClaimDepositPerByte::get().saturating_mul(bytes);
+			T::ClaimDepositBase::get().saturating_add(per_byte)
+		}
 	}
 }
@@ -0,0 +1,131 @@
+//! 批量存证用的Merkle树
+//!
+//! 叶子是文件内容的摘要，所有节点都使用blake2-256计算：叶子节点为`blake2_256(0x00 ++ leaf)`，
+//! 中间节点为`blake2_256(0x01 ++ left ++ right)`，区分两种节点可以防止用中间节点冒充叶子。
+//! 某一层的节点个数是奇数时，最后一个节点直接提升到上一层，不和自己配对。
+//!
+//! 链下用`MerkleTree`构造树和每个叶子的证明，链上用`verify`校验证明。
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_std::{vec, vec::Vec};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// 叶子节点的哈希
+pub fn hash_leaf(leaf: &H256) -> H256 {
+	let mut data = [0u8; 33];
+	data[0] = LEAF_PREFIX;
+	data[1..].copy_from_slice(leaf.as_bytes());
+	sp_io::hashing::blake2_256(&data).into()
+}
+
+fn hash_node(left: &H256, right: &H256) -> H256 {
+	let mut data = [0u8; 65];
+	data[0] = NODE_PREFIX;
+	data[1..33].copy_from_slice(left.as_bytes());
+	data[33..].copy_from_slice(right.as_bytes());
+	sp_io::hashing::blake2_256(&data).into()
+}
+
+/// 叶子包含在某个批次中的证明
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct MerkleProof {
+	/// 叶子在批次中的位置，从0开始
+	pub leaf_index: u32,
+	/// 批次中叶子的数量
+	pub leaf_count: u32,
+	/// 从叶子往上每一层的兄弟节点，被直接提升的节点没有兄弟节点
+	pub siblings: Vec<H256>,
+}
+
+/// Merkle树，保存了从叶子到根的每一层节点
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MerkleTree {
+	layers: Vec<Vec<H256>>,
+}
+
+impl MerkleTree {
+	/// 用文件摘要构造Merkle树，没有叶子时返回None
+	pub fn new(leaves: &[H256]) -> Option<Self> {
+		if leaves.is_empty() || leaves.len() > u32::MAX as usize {
+			return None
+		}
+
+		let mut layers = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+		loop {
+			let layer = layers.last().expect("layers is not empty; qed");
+			if layer.len() == 1 {
+				break
+			}
+			let next = layer
+				.chunks(2)
+				.map(|pair| match pair {
+					[left, right] => hash_node(left, right),
+					// 奇数个节点时最后一个直接提升
+					_ => pair[0],
+				})
+				.collect();
+			layers.push(next);
+		}
+		Some(MerkleTree { layers })
+	}
+
+	pub fn root(&self) -> H256 {
+		self.layers.last().expect("layers is not empty; qed")[0]
+	}
+
+	pub fn leaf_count(&self) -> u32 {
+		self.layers[0].len() as u32
+	}
+
+	/// 第leaf_index个叶子的证明，超出范围时返回None
+	pub fn proof(&self, leaf_index: u32) -> Option<MerkleProof> {
+		let mut index = leaf_index as usize;
+		if index >= self.layers[0].len() {
+			return None
+		}
+
+		let mut siblings = Vec::new();
+		for layer in &self.layers[..self.layers.len() - 1] {
+			if let Some(sibling) = layer.get(index ^ 1) {
+				siblings.push(*sibling);
+			}
+			index /= 2;
+		}
+		Some(MerkleProof { leaf_index, leaf_count: self.leaf_count(), siblings })
+	}
+}
+
+/// 校验leaf是否包含在以root为根、有proof.leaf_count个叶子的树中
+pub fn verify(root: &H256, leaf: &H256, proof: &MerkleProof) -> bool {
+	if proof.leaf_index >= proof.leaf_count {
+		return false
+	}
+
+	let mut hash = hash_leaf(leaf);
+	let mut index = proof.leaf_index;
+	let mut width = proof.leaf_count;
+	let mut siblings = proof.siblings.iter();
+	while width > 1 {
+		// 这一层的最后一个节点没有兄弟节点时直接提升
+		if (index ^ 1) < width {
+			let sibling = match siblings.next() {
+				Some(sibling) => sibling,
+				None => return false,
+			};
+			hash = if index % 2 == 0 {
+				hash_node(&hash, sibling)
+			} else {
+				hash_node(sibling, &hash)
+			};
+		}
+		index /= 2;
+		// 向上取整，leaf_count是u32::MAX时(width + 1)会溢出
+		width = width / 2 + width % 2;
+	}
+	siblings.next().is_none() && hash == *root
+}
@@ -0,0 +1,13 @@
+//! poe的存储迁移
+//!
+//! 和kitties一样，每个`MigrateToVn`只在链上版本是上一个版本时执行，`Migrations`按顺序包含了
+//! 所有的迁移。
+
+pub mod v1;
+pub mod v2;
+pub mod v3;
+
+pub(crate) const LOG_TARGET: &str = "runtime::poe";
+
+/// 从v0依次升级到当前版本
+pub type Migrations<T> = (v1::MigrateToV1<T>, v2::MigrateToV2<T>, v3::MigrateToV3<T>);
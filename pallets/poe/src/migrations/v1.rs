@@ -0,0 +1,78 @@
+use super::LOG_TARGET;
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// v0和v1的存证: (owner, 创建或者转移时的区块)
+pub type OldProof<T> =
+	(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber);
+
+/// v0的存证直接用原始内容作为key
+pub mod v0 {
+	use super::*;
+
+	#[frame_support::storage_alias]
+	pub type Proofs<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, Vec<u8>, OldProof<T>>;
+}
+
+/// v1格式的Proofs存储，v2之前用它读写存证
+#[frame_support::storage_alias]
+pub type Proofs<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, ContentHash, OldProof<T>>;
+
+/// 把原始内容的存证换成blake2-256摘要，和`create_claim`在链上计算的摘要一致
+pub struct MigrateToV1<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 0 {
+			log::info!(target: LOG_TARGET, "skipping v1 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		// 新旧数据在同一个存储前缀下，先全部取出再写入，避免遍历到新写入的key
+		let old: Vec<_> = v0::Proofs::<T>::drain().collect();
+		let count = old.len() as u64;
+		for (claim, proof) in old {
+			Proofs::<T>::insert(ContentHash::of(HashAlgorithm::Blake2_256, &claim), proof);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "hashed {} claims in v1", count);
+
+		// 版本和旧存证的读取，旧存证的删除、新存证和版本的写入
+		T::DbWeight::get().reads_writes(count + 1, count * 2 + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let proofs = if Pallet::<T>::on_chain_storage_version() == 0 {
+			Some(v0::Proofs::<T>::iter().collect::<Vec<_>>())
+		} else {
+			None
+		};
+		Ok(proofs.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let proofs: Option<Vec<(Vec<u8>, OldProof<T>)>> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v1: invalid pre-upgrade state")?;
+		let proofs = match proofs {
+			Some(proofs) => proofs,
+			None => return Ok(()),
+		};
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 1, "v1: storage version not updated");
+		ensure!(Proofs::<T>::iter_keys().count() == proofs.len(), "v1: claim count changed");
+		for (claim, proof) in proofs {
+			let claim_hash = ContentHash::of(HashAlgorithm::Blake2_256, &claim);
+			ensure!(Proofs::<T>::get(claim_hash) == Some(proof), "v1: claim not migrated");
+		}
+		Ok(())
+	}
+}
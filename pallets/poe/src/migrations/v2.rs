@@ -0,0 +1,91 @@
+use super::{v1, LOG_TARGET};
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// v2格式的存证，v3增加了过期区块
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProofV2<AccountId, BlockNumber, Moment, Metadata> {
+	pub owner: AccountId,
+	pub block_number: BlockNumber,
+	pub created_at: Moment,
+	pub metadata: Metadata,
+}
+
+pub type ProofV2Of<T> = ProofV2<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	MomentOf<T>,
+	ClaimMetadataOf<T>,
+>;
+
+/// v2格式的Proofs存储，v3之前用它读写存证
+#[frame_support::storage_alias]
+pub type Proofs<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, ContentHash, ProofV2Of<T>>;
+
+/// 给已有的存证加上空的描述信息，原来没有记录创建时间，created_at为0
+pub struct MigrateToV2<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 1 {
+			log::info!(target: LOG_TARGET, "skipping v2 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count = 0u64;
+		Proofs::<T>::translate::<v1::OldProof<T>, _>(|_, (owner, block_number)| {
+			count += 1;
+			Some(ProofV2 {
+				owner,
+				block_number,
+				created_at: Default::default(),
+				metadata: Default::default(),
+			})
+		});
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "added metadata to {} claims in v2", count);
+
+		T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let proofs = if Pallet::<T>::on_chain_storage_version() == 1 {
+			Some(v1::Proofs::<T>::iter().collect::<Vec<_>>())
+		} else {
+			None
+		};
+		Ok(proofs.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let proofs: Option<Vec<(ContentHash, v1::OldProof<T>)>> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v2: invalid pre-upgrade state")?;
+		let proofs = match proofs {
+			Some(proofs) => proofs,
+			None => return Ok(()),
+		};
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 2, "v2: storage version not updated");
+		ensure!(Proofs::<T>::iter_keys().count() == proofs.len(), "v2: claim count changed");
+		for (claim_hash, (owner, block_number)) in proofs {
+			let proof = Proofs::<T>::get(claim_hash).ok_or("v2: claim lost")?;
+			ensure!(
+				proof.owner == owner && proof.block_number == block_number,
+				"v2: claim owner changed"
+			);
+			ensure!(proof.metadata == Default::default(), "v2: unexpected metadata");
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,77 @@
+use super::{v2, LOG_TARGET};
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// 已有的存证都是永久有效的，并从当前区块开始清理过期存证
+pub struct MigrateToV3<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 2 {
+			log::info!(target: LOG_TARGET, "skipping v3 migration, on-chain {:?}", on_chain_version);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut count = 0u64;
+		Proofs::<T>::translate::<v2::ProofV2Of<T>, _>(|_, proof| {
+			count += 1;
+			Some(Proof {
+				owner: proof.owner,
+				block_number: proof.block_number,
+				created_at: proof.created_at,
+				metadata: proof.metadata,
+				expires_at: None,
+			})
+		});
+		// 不需要从创世区块开始检查过期列表
+		NextExpiryBlock::<T>::put(frame_system::Pallet::<T>::block_number());
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+		log::info!(target: LOG_TARGET, "migrated {} claims to v3", count);
+
+		T::DbWeight::get().reads_writes(count + 2, count + 2)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		let proofs = if Pallet::<T>::on_chain_storage_version() == 2 {
+			Some(v2::Proofs::<T>::iter().collect::<Vec<_>>())
+		} else {
+			None
+		};
+		Ok(proofs.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		let proofs: Option<Vec<(ContentHash, v2::ProofV2Of<T>)>> =
+			Decode::decode(&mut &state[..]).map_err(|_| "v3: invalid pre-upgrade state")?;
+		let proofs = match proofs {
+			Some(proofs) => proofs,
+			None => return Ok(()),
+		};
+
+		ensure!(Pallet::<T>::on_chain_storage_version() == 3, "v3: storage version not updated");
+		ensure!(Proofs::<T>::iter_keys().count() == proofs.len(), "v3: claim count changed");
+		for (claim_hash, old) in proofs {
+			let proof = Proofs::<T>::get(claim_hash).ok_or("v3: claim lost")?;
+			ensure!(
+				proof.owner == old.owner &&
+					proof.block_number == old.block_number &&
+					proof.created_at == old.created_at &&
+					proof.metadata == old.metadata,
+				"v3: claim changed"
+			);
+			ensure!(proof.expires_at.is_none(), "v3: unexpected expiry");
+		}
+		Ok(())
+	}
+}
@@ -1,5 +1,8 @@
 use crate as pallet_poe;
-use frame_support::traits::{ConstU16, ConstU64, ConstU32};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU64, ConstU32},
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -17,6 +20,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
 		PoeModule: pallet_poe,
 	}
 );
@@ -39,7 +44,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -48,12 +53,63 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = ();
+	type MaxHolds = ();
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	// 测试中可以修改
+	pub static AllowReclaim: bool = false;
+}
+
 impl pallet_poe::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxClaimLength = ConstU32<10>;
+	type Time = Timestamp;
+	type Currency = Balances;
+	type ClaimDepositBase = ConstU64<10>;
+	type ClaimDepositPerByte = ConstU64<1>;
+	type MaxDescriptionLength = ConstU32<16>;
+	type MaxMimeTypeLength = ConstU32<16>;
+	type MaxUriLength = ConstU32<32>;
+	type MaxExpiringPerBlock = ConstU32<4>;
+	type ExpiryBatchSize = ConstU32<3>;
+	type MaxCosigners = ConstU32<3>;
+	type PendingClaimTimeout = ConstU64<5>;
+	type MaxPendingPerBlock = ConstU32<2>;
+	type MaxHistoryLength = ConstU32<3>;
+	type MaxReasonLength = ConstU32<16>;
+	type AllowReclaim = AllowReclaim;
+	type WeightInfo = ();
 }
 
+// 账户3的余额不够支付存证的质押
+pub const POOR: u64 = 3;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (2, 1_000), (POOR, 20)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
 }
@@ -0,0 +1,43 @@
+//! 给runtime api使用的查询方法
+use crate::*;
+use sp_core::H256;
+use sp_std::vec::Vec;
+
+impl<T: Config> Pallet<T> {
+	/// leaf是否包含在以root为根的批量存证中，root必须已经在链上存证
+	pub fn verify_inclusion(leaf: H256, proof: MerkleProof, root: H256) -> bool {
+		match Self::anchors(root) {
+			Some(anchor) =>
+				anchor.leaf_count == proof.leaf_count && merkle::verify(&root, &leaf, &proof),
+			None => false,
+		}
+	}
+
+	/// 摘要对应的存证现在的状态，从来没有创建过时返回None。
+	/// 已经过期但是还没有被清理的存证也是Expired
+	pub fn claim_status(claim_hash: ContentHash) -> Option<ClaimStatus> {
+		if let Some(proof) = Self::proofs(claim_hash) {
+			if Self::is_expired(&proof) {
+				return Some(ClaimStatus::Expired)
+			}
+			return Some(ClaimStatus::Active)
+		}
+		if PendingClaims::<T>::contains_key(claim_hash) {
+			return Some(ClaimStatus::Pending)
+		}
+		Self::tombstones(claim_hash).map(|tombstone| match tombstone.cause {
+			TombstoneCause::Revoked { .. } => ClaimStatus::Revoked,
+			TombstoneCause::Expired => ClaimStatus::Expired,
+		})
+	}
+
+	/// 存证在`Proofs`中的存储key，RPC用它生成存储证明
+	pub fn proof_storage_key(claim_hash: ContentHash) -> Vec<u8> {
+		Proofs::<T>::hashed_key_for(claim_hash)
+	}
+
+	/// 存证从创建开始的所有权历史，最后一条记录的owner是现在的owner
+	pub fn custody_history(claim_hash: ContentHash) -> Vec<CustodyRecordOf<T>> {
+		ClaimHistory::<T>::get(claim_hash).into_inner()
+	}
+}
@@ -1,29 +1,121 @@
 use super::*;
 use crate::{mock::*, Error, Event};
+use codec::Encode;
 use frame_support::{assert_noop, assert_ok, BoundedVec};
 
+// create_claim在链上计算的摘要
+fn hash_of(claim: &[u8]) -> ContentHash {
+  ContentHash::of(HashAlgorithm::Blake2_256, claim)
+}
+
+fn metadata() -> ClaimMetadataOf<Test> {
+  ClaimMetadata {
+    description: BoundedVec::try_from(b"contract".to_vec()).unwrap(),
+    mime_type: BoundedVec::try_from(b"application/pdf".to_vec()).unwrap(),
+    uri: Some(BoundedVec::try_from(b"ipfs://contract".to_vec()).unwrap()),
+  }
+}
 
+fn reason() -> BoundedVec<u8, <Test as Config>::MaxReasonLength> {
+  BoundedVec::try_from(b"superseded".to_vec()).unwrap()
+}
+
+// 失效的记录现在的质押
+fn tombstone_deposit(claim_hash: ContentHash) -> u64 {
+  let tombstone = PoeModule::tombstones(claim_hash).unwrap();
+  PoeModule::tombstone_deposit(&claim_hash, &tombstone)
+}
+
+// (owner, 创建或者转移时的区块)
+fn owner_and_block(claim_hash: ContentHash) -> Option<(u64, u64)> {
+  Proofs::<Test>::get(claim_hash).map(|proof| (proof.owner, proof.block_number))
+}
 
 #[test]
 fn create_claim_works() {
   new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    Timestamp::set_timestamp(1_000);
     let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
 
-    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone()));
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
     
     // 判断链上数据
-    assert_eq!(Proofs::<Test>::get(&claim), Some((1, frame_system::Pallet::<Test>::block_number())));
+    assert_eq!(
+      PoeModule::proofs(hash_of(&claim)),
+      Some(Proof { owner: 1, block_number: 1, created_at: 1_000, metadata: metadata(), expires_at: None })
+    );
+    System::assert_last_event(Event::ClaimCreated(1, hash_of(&claim), metadata(), 1_000).into());
+  })
+}
+
+#[test]
+fn create_claim_for_hash_works() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let claim_hash = ContentHash::of(HashAlgorithm::Keccak256, b"document");
+
+    assert_ok!(PoeModule::create_claim_for_hash(RuntimeOrigin::signed(1), claim_hash, metadata(), None));
+
+    assert_eq!(owner_and_block(claim_hash), Some((1, 1)));
+    System::assert_last_event(Event::ClaimCreated(1, claim_hash, metadata(), 0).into());
+  })
+}
+
+#[test]
+fn create_claim_for_hash_matches_raw_claim() {
+  new_test_ext().execute_with(|| {
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+
+    // 链下用同样的算法计算出的摘要就是同一个存证
+    assert_noop!(
+      PoeModule::create_claim_for_hash(RuntimeOrigin::signed(2), hash_of(&claim), metadata(), None),
+      Error::<Test>::ProofAlreadyExist,
+    );
+    // 不同算法的摘要是不同的存证
+    let sha2 = ContentHash::of(HashAlgorithm::Sha2_256, &claim);
+    assert_ok!(PoeModule::create_claim_for_hash(RuntimeOrigin::signed(2), sha2, metadata(), None));
+    assert_eq!(Proofs::<Test>::get(sha2).map(|proof| proof.owner), Some(2));
   })
 }
 
+#[test]
+fn content_hash_uses_the_algorithm() {
+  let content = b"hello";
+  assert_eq!(
+    ContentHash::of(HashAlgorithm::Blake2_256, content).digest,
+    sp_io::hashing::blake2_256(content)
+  );
+  assert_eq!(
+    ContentHash::of(HashAlgorithm::Sha2_256, content).digest,
+    sp_io::hashing::sha2_256(content)
+  );
+  assert_eq!(
+    ContentHash::of(HashAlgorithm::Keccak256, content).digest,
+    sp_io::hashing::keccak_256(content)
+  );
+  assert_eq!(HashAlgorithm::default(), HashAlgorithm::Blake2_256);
+}
+
+#[test]
+fn hash_file_matches_on_chain_hash() {
+  let path = std::env::temp_dir().join(format!("poe-hash-file-{}", std::process::id()));
+  std::fs::write(&path, b"document").unwrap();
+  let claim_hash = crate::hashing::hash_file(HashAlgorithm::Sha2_256, &path).unwrap();
+  std::fs::remove_file(&path).unwrap();
+
+  assert_eq!(claim_hash, ContentHash::of(HashAlgorithm::Sha2_256, b"document"));
+}
+
 #[test]
 fn create_claim_failed_when_claim_already_exists() {
   new_test_ext().execute_with(|| {
     let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
-    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None);
 
     assert_noop!(
-      PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone()),
+      PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None),
       Error::<Test>::ProofAlreadyExist,
     );
   })
@@ -33,19 +125,20 @@ fn create_claim_failed_when_claim_already_exists() {
 fn revoke_claim_works() {
   new_test_ext().execute_with(|| {
     let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
-    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None);
 
-    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()));
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&claim), 1, reason()));
+    assert!(!Proofs::<Test>::contains_key(hash_of(&claim)));
   })
 }
 
 #[test]
 fn revoke_claim_failed_when_claim_is_not_exist() {
   new_test_ext().execute_with(|| {
-    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    let claim = vec![0, 1];
 
     assert_noop!(
-      PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()),
+      PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&claim), 1, reason()),
       Error::<Test>::ClaimNotExist
     );
   })
@@ -55,10 +148,10 @@ fn revoke_claim_failed_when_claim_is_not_exist() {
 fn revoke_claim_faile_with_wrong_owner() {
   new_test_ext().execute_with(|| {
     let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
-    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None);
 
     assert_noop!(
-      PoeModule::revoke_claim(RuntimeOrigin::signed(2), claim.clone()),
+      PoeModule::revoke_claim(RuntimeOrigin::signed(2), hash_of(&claim), 1, reason()),
       Error::<Test>::NotClaimOwner
     );
   })
@@ -68,12 +161,14 @@ fn revoke_claim_faile_with_wrong_owner() {
 fn transfer_claim_works() {
   new_test_ext().execute_with(|| {
     let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
-    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None);
 
-    assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim.clone(), 2));
+    assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), hash_of(&claim), 2));
 
     // 判断链上数据是否正确
-    assert_eq!(Proofs::<Test>::get(&claim), Some((2, frame_system::Pallet::<Test>::block_number())));
+    assert_eq!(owner_and_block(hash_of(&claim)), Some((2, frame_system::Pallet::<Test>::block_number())));
+    // 描述信息不变
+    assert_eq!(PoeModule::proofs(hash_of(&claim)).map(|proof| proof.metadata), Some(metadata()));
   })
 }
 
@@ -81,18 +176,965 @@ fn transfer_claim_works() {
 fn transfer_claim_failed_with_wrong_owner() {
   new_test_ext().execute_with(|| {
     let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
-    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+    let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None);
 
-    assert_noop!(PoeModule::transfer_claim(RuntimeOrigin::signed(2), claim.clone(), 2), Error::<Test>::NotClaimOwner);
+    assert_noop!(PoeModule::transfer_claim(RuntimeOrigin::signed(2), hash_of(&claim), 2), Error::<Test>::NotClaimOwner);
   })
 }
 
 #[test]
 fn transfer_claim_failed_when_claim_is_not_exist() {
   new_test_ext().execute_with(|| {
+    let claim = vec![0, 1];
+    // let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None);
+
+    assert_noop!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), hash_of(&claim), 2), Error::<Test>::ClaimNotExist);
+  })
+}
+
+// 直接写入v0格式的存证，key是原始内容
+fn put_raw_claim(claim: &[u8], proof: (u64, u64)) {
+  crate::migrations::v1::v0::Proofs::<Test>::insert(claim.to_vec(), proof);
+}
+
+// 依次执行所有迁移，打开try-runtime时同时执行pre_upgrade和post_upgrade检查
+fn run_migrations() {
+  use crate::migrations::{v1::MigrateToV1, v2::MigrateToV2, v3::MigrateToV3};
+  use frame_support::traits::OnRuntimeUpgrade;
+
+  fn run<M: OnRuntimeUpgrade>() {
+    #[cfg(feature = "try-runtime")]
+    let state = M::pre_upgrade().unwrap();
+    M::on_runtime_upgrade();
+    #[cfg(feature = "try-runtime")]
+    M::post_upgrade(state).unwrap();
+  }
+
+  run::<MigrateToV1<Test>>();
+  run::<MigrateToV2<Test>>();
+  run::<MigrateToV3<Test>>();
+}
+
+#[test]
+fn migrate_raw_claims_to_hashes() {
+  use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(0).put::<PoeModule>();
+    put_raw_claim(&[0, 1], (1, 3));
+    put_raw_claim(&[2, 3, 4], (2, 5));
+
+    run_migrations();
+
+    assert_eq!(PoeModule::on_chain_storage_version(), 3);
+    assert_eq!(Proofs::<Test>::iter().count(), 2);
+    assert_eq!(owner_and_block(hash_of(&[0, 1])), Some((1, 3)));
+    assert_eq!(owner_and_block(hash_of(&[2, 3, 4])), Some((2, 5)));
+
+    // 迁移后原来的owner可以继续用原始内容的摘要操作存证
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&[0, 1]), 1, reason()));
+
+    // 再次执行不会改变数据
+    let proofs: Vec<_> = Proofs::<Test>::iter().collect();
+    run_migrations();
+    assert_eq!(Proofs::<Test>::iter().collect::<Vec<_>>(), proofs);
+  })
+}
+
+#[test]
+fn migrate_hashed_claims_adds_empty_metadata() {
+  use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(1).put::<PoeModule>();
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"document");
+    crate::migrations::v1::Proofs::<Test>::insert(claim_hash, (1, 3));
+
+    run_migrations();
+
+    assert_eq!(PoeModule::on_chain_storage_version(), 3);
+    assert_eq!(
+      PoeModule::proofs(claim_hash),
+      Some(Proof {
+        owner: 1,
+        block_number: 3,
+        created_at: 0,
+        metadata: Default::default(),
+        expires_at: None,
+      })
+    );
+  })
+}
+
+#[test]
+fn migrate_v2_claims_never_expire() {
+  use crate::migrations::v2::{self, ProofV2};
+  use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    System::set_block_number(7);
+    StorageVersion::new(2).put::<PoeModule>();
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"document");
+    v2::Proofs::<Test>::insert(
+      claim_hash,
+      ProofV2 { owner: 1, block_number: 3, created_at: 1_000, metadata: metadata() },
+    );
+
+    run_migrations();
+
+    assert_eq!(PoeModule::on_chain_storage_version(), 3);
+    assert_eq!(
+      PoeModule::proofs(claim_hash),
+      Some(Proof { owner: 1, block_number: 3, created_at: 1_000, metadata: metadata(), expires_at: None })
+    );
+    // 从迁移时的区块开始清理
+    assert_eq!(NextExpiryBlock::<Test>::get(), 7);
+  })
+}
+
+fn run_to_block(n: u64) {
+  use frame_support::traits::Hooks;
+
+  while System::block_number() < n {
+    let next = System::block_number() + 1;
+    System::set_block_number(next);
+    PoeModule::on_initialize(next);
+  }
+}
+
+// 在expires_at过期的存证
+fn create_expiring(who: u64, content: &[u8], expires_at: u64) -> ContentHash {
+  let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, content);
+  assert_ok!(PoeModule::create_claim_for_hash(
+    RuntimeOrigin::signed(who),
+    claim_hash,
+    metadata(),
+    Some(expires_at)
+  ));
+  claim_hash
+}
+
+#[test]
+fn create_claim_failed_with_past_expiry() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(5);
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"document");
+
+    assert_noop!(
+      PoeModule::create_claim_for_hash(RuntimeOrigin::signed(1), claim_hash, metadata(), Some(5)),
+      Error::<Test>::InvalidExpiry
+    );
+  })
+}
+
+#[test]
+fn claim_expires_at_its_block() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 3);
+    assert_eq!(ClaimsExpiringAt::<Test>::get(3).into_inner(), vec![claim_hash]);
+
+    run_to_block(2);
+    assert!(Proofs::<Test>::contains_key(claim_hash));
+
+    run_to_block(3);
+    assert!(!Proofs::<Test>::contains_key(claim_hash));
+    assert!(!ClaimsExpiringAt::<Test>::contains_key(3));
+    assert_eq!(NextExpiryBlock::<Test>::get(), 4);
+    System::assert_last_event(Event::ClaimExpired(1, claim_hash).into());
+
+    // 过期之后默认不能重新创建
+    assert_noop!(
+      PoeModule::create_claim_for_hash(RuntimeOrigin::signed(2), claim_hash, metadata(), None),
+      Error::<Test>::ClaimTombstoned
+    );
+  })
+}
+
+#[test]
+fn renew_claim_works() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 3);
+
+    assert_noop!(
+      PoeModule::renew_claim(RuntimeOrigin::signed(2), claim_hash, Some(5)),
+      Error::<Test>::NotClaimOwner
+    );
+    assert_noop!(
+      PoeModule::renew_claim(RuntimeOrigin::signed(1), claim_hash, Some(1)),
+      Error::<Test>::InvalidExpiry
+    );
+
+    assert_ok!(PoeModule::renew_claim(RuntimeOrigin::signed(1), claim_hash, Some(5)));
+    System::assert_last_event(Event::ClaimRenewed(1, claim_hash, Some(5)).into());
+    assert!(ClaimsExpiringAt::<Test>::get(3).is_empty());
+    assert_eq!(ClaimsExpiringAt::<Test>::get(5).into_inner(), vec![claim_hash]);
+
+    run_to_block(4);
+    assert!(Proofs::<Test>::contains_key(claim_hash));
+
+    // 续期为永久有效
+    assert_ok!(PoeModule::renew_claim(RuntimeOrigin::signed(1), claim_hash, None));
+    assert!(ClaimsExpiringAt::<Test>::get(5).is_empty());
+    run_to_block(6);
+    assert_eq!(PoeModule::proofs(claim_hash).and_then(|proof| proof.expires_at), None);
+    assert!(Proofs::<Test>::contains_key(claim_hash));
+  })
+}
+
+#[test]
+fn expired_claim_cannot_be_used_before_sweep() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 3);
+
+    // 没有执行on_initialize，存证还没有被清理
+    System::set_block_number(3);
+    assert!(Proofs::<Test>::contains_key(claim_hash));
+    assert_noop!(
+      PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim_hash, 2),
+      Error::<Test>::ClaimExpired
+    );
+    assert_noop!(
+      PoeModule::renew_claim(RuntimeOrigin::signed(1), claim_hash, Some(10)),
+      Error::<Test>::ClaimExpired
+    );
+    assert_noop!(
+      PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim_hash, 1, reason()),
+      Error::<Test>::ClaimExpired
+    );
+  })
+}
+
+#[test]
+fn revoke_claim_removes_expiry_schedule() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 3);
+
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim_hash, 1, reason()));
+    assert!(ClaimsExpiringAt::<Test>::get(3).is_empty());
+
+    // 别人在过期区块之前重新创建的永久存证不会被清理
+    AllowReclaim::set(&true);
+    assert_ok!(PoeModule::create_claim_for_hash(RuntimeOrigin::signed(2), claim_hash, metadata(), None));
+    run_to_block(3);
+    assert!(Proofs::<Test>::contains_key(claim_hash));
+  })
+}
+
+#[test]
+fn too_many_claims_expiring_in_one_block() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    // MaxExpiringPerBlock是4
+    for i in 0u8..4 {
+      create_expiring(1, &[i], 3);
+    }
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"document");
+
+    assert_noop!(
+      PoeModule::create_claim_for_hash(RuntimeOrigin::signed(1), claim_hash, metadata(), Some(3)),
+      Error::<Test>::TooManyExpiring
+    );
+  })
+}
+
+#[test]
+fn expired_claims_are_swept_in_batches() {
+  use frame_support::{traits::Hooks, weights::Weight};
+
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claims: Vec<_> = (0u8..4).map(|i| create_expiring(1, &[i], 2)).collect();
+
+    // ExpiryBatchSize是3: 读取列表算一次，只能清理2个
+    run_to_block(2);
+    assert_eq!(claims.iter().filter(|hash| Proofs::<Test>::contains_key(hash)).count(), 2);
+    assert_eq!(ClaimsExpiringAt::<Test>::get(2).len(), 2);
+    assert_eq!(NextExpiryBlock::<Test>::get(), 2);
+
+    // 没有剩余空间时on_idle什么也不做
+    assert_eq!(PoeModule::on_idle(2, Weight::zero()), Weight::zero());
+    assert_eq!(ClaimsExpiringAt::<Test>::get(2).len(), 2);
+
+    // 剩下的在on_idle中清理
+    PoeModule::on_idle(2, Weight::MAX);
+    assert!(claims.iter().all(|hash| !Proofs::<Test>::contains_key(hash)));
+    assert!(!ClaimsExpiringAt::<Test>::contains_key(2));
+    assert_eq!(NextExpiryBlock::<Test>::get(), 3);
+  })
+}
+
+#[test]
+fn create_claim_reserves_deposit() {
+  new_test_ext().execute_with(|| {
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+
+    // 基础质押10，存证和创建记录每个字节1
+    let proof = PoeModule::proofs(hash_of(&claim)).unwrap();
+    let record = CustodyRecord { owner: 1u64, block_number: 0u64, action: CustodyAction::Created };
+    assert_eq!(PoeModule::deposit_for(&proof), 10 + proof.encoded_size() as u64);
+    let deposit = 10 + (proof.encoded_size() + record.encoded_size()) as u64;
+    assert_eq!(PoeModule::claim_deposit(hash_of(&claim)), deposit);
+    assert_eq!(Balances::reserved_balance(1), deposit);
+    assert_eq!(Balances::free_balance(1), 1_000 - deposit);
+  })
+}
+
+#[test]
+fn create_claim_failed_with_insufficient_balance() {
+  new_test_ext().execute_with(|| {
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+
+    assert_noop!(
+      PoeModule::create_claim(RuntimeOrigin::signed(POOR), claim, metadata(), None),
+      Error::<Test>::InsufficientBalance
+    );
+  })
+}
+
+#[test]
+fn revoke_claim_releases_deposit() {
+  new_test_ext().execute_with(|| {
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&claim), 1, reason()));
+
+    // 只保留失效的记录和历史记录的质押: 基础质押10，每个字节1
+    let tombstone = PoeModule::tombstones(hash_of(&claim)).unwrap();
+    let history = ClaimHistory::<Test>::get(hash_of(&claim));
+    let deposit = 10 + (tombstone.encoded_size() + history.encoded_size()) as u64;
+    assert_eq!(PoeModule::claim_deposit(hash_of(&claim)), deposit);
+    assert_eq!(Balances::reserved_balance(1), deposit);
+    assert_eq!(Balances::free_balance(1), 1_000 - deposit);
+
+    // 取回失效的记录的质押之后全部退回
+    assert_ok!(PoeModule::release_tombstone_deposit(RuntimeOrigin::signed(1), hash_of(&claim)));
+    assert_eq!(PoeModule::claim_deposit(hash_of(&claim)), 0);
+    assert_eq!(Balances::reserved_balance(1), 0);
+    assert_eq!(Balances::free_balance(1), 1_000);
+  })
+}
+
+#[test]
+fn revoke_claim_reserves_tombstone_deposit() {
+  new_test_ext().execute_with(|| {
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"document");
+    let empty = ClaimMetadata {
+      description: Default::default(),
+      mime_type: Default::default(),
+      uri: None,
+    };
+    assert_ok!(PoeModule::create_claim_for_hash(RuntimeOrigin::signed(1), claim_hash, empty, None));
+    let created = PoeModule::claim_deposit(claim_hash);
+
+    // 撤销原因(MaxReasonLength是16)比存证长，需要补足质押
+    let long_reason = BoundedVec::try_from(vec![b'a'; 16]).unwrap();
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim_hash, 1, long_reason));
+    let deposit = tombstone_deposit(claim_hash);
+    assert!(deposit > created);
+    assert_eq!(PoeModule::claim_deposit(claim_hash), deposit);
+    assert_eq!(Balances::reserved_balance(1), deposit);
+  })
+}
+
+#[test]
+fn transfer_claim_moves_deposit() {
+  new_test_ext().execute_with(|| {
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+    let created = PoeModule::claim_deposit(hash_of(&claim));
+
+    assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), hash_of(&claim), 2));
+
+    // 原来的owner为转移记录(17个字节)质押，质押随存证转移到新的owner，撤销时退回给新的owner
+    let deposit = created + 17;
+    assert_eq!(PoeModule::claim_deposit(hash_of(&claim)), deposit);
+    assert_eq!(Balances::reserved_balance(1), 0);
+    assert_eq!(Balances::free_balance(1), 1_000 - deposit);
+    assert_eq!(Balances::reserved_balance(2), deposit);
+
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(2), hash_of(&claim), 1, reason()));
+    let kept = tombstone_deposit(hash_of(&claim));
+    assert_eq!(Balances::reserved_balance(2), kept);
+    assert_eq!(Balances::free_balance(2), 1_000 + deposit - kept);
+  })
+}
+
+#[test]
+fn expired_claim_releases_deposit() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 3);
+    assert!(Balances::reserved_balance(1) > 0);
+
+    run_to_block(3);
+
+    assert!(!Proofs::<Test>::contains_key(claim_hash));
+    let kept = tombstone_deposit(claim_hash);
+    assert_eq!(PoeModule::claim_deposit(claim_hash), kept);
+    assert_eq!(Balances::reserved_balance(1), kept);
+
+    assert_ok!(PoeModule::release_tombstone_deposit(RuntimeOrigin::signed(1), claim_hash));
+    assert_eq!(Balances::reserved_balance(1), 0);
+    assert_eq!(Balances::free_balance(1), 1_000);
+  })
+}
+
+// 批量存证的叶子
+fn leaves(count: u8) -> Vec<sp_core::H256> {
+  (0..count).map(|i| sp_core::H256::repeat_byte(i + 1)).collect()
+}
+
+#[test]
+fn merkle_proofs_verify_for_every_leaf() {
+  new_test_ext().execute_with(|| {
+    assert!(merkle::MerkleTree::new(&[]).is_none());
+
+    // 包括叶子个数是奇数、需要提升节点的情况
+    for count in 1..=7 {
+      let leaves = leaves(count);
+      let tree = merkle::MerkleTree::new(&leaves).unwrap();
+      assert_eq!(tree.leaf_count(), count as u32);
+      for (index, leaf) in leaves.iter().enumerate() {
+        let proof = tree.proof(index as u32).unwrap();
+        assert!(merkle::verify(&tree.root(), leaf, &proof));
+      }
+      assert!(tree.proof(count as u32).is_none());
+    }
+  })
+}
+
+#[test]
+fn merkle_proof_rejects_tampering() {
+  new_test_ext().execute_with(|| {
+    let leaves = leaves(5);
+    let tree = merkle::MerkleTree::new(&leaves).unwrap();
+    let proof = tree.proof(2).unwrap();
+
+    // 换了叶子
+    assert!(!merkle::verify(&tree.root(), &leaves[3], &proof));
+    // 换了位置
+    let wrong_index = MerkleProof { leaf_index: 3, ..proof.clone() };
+    assert!(!merkle::verify(&tree.root(), &leaves[2], &wrong_index));
+    // 多了兄弟节点
+    let mut extra = proof.clone();
+    extra.siblings.push(leaves[0]);
+    assert!(!merkle::verify(&tree.root(), &leaves[2], &extra));
+    // 叶子的哈希不能当作中间节点
+    let single = merkle::MerkleTree::new(&leaves[..1]).unwrap();
+    assert_eq!(single.root(), merkle::hash_leaf(&leaves[0]));
+    assert!(!merkle::verify(&single.root(), &single.root(), &single.proof(0).unwrap()));
+  })
+}
+
+#[test]
+fn anchor_batch_works() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    Timestamp::set_timestamp(1_000);
+    let tree = merkle::MerkleTree::new(&leaves(3)).unwrap();
+
+    assert_ok!(PoeModule::anchor_batch(RuntimeOrigin::signed(1), tree.root(), 3, metadata()));
+
+    let anchor = PoeModule::anchors(tree.root()).unwrap();
+    assert_eq!((anchor.owner, anchor.leaf_count, anchor.created_at), (1, 3, 1_000));
+    assert_eq!(Balances::reserved_balance(1), anchor.deposit);
+    System::assert_last_event(Event::BatchAnchored(1, tree.root(), 3, metadata(), 1_000).into());
+  })
+}
+
+#[test]
+fn anchor_batch_failed() {
+  new_test_ext().execute_with(|| {
+    let root = merkle::MerkleTree::new(&leaves(3)).unwrap().root();
+
+    assert_noop!(
+      PoeModule::anchor_batch(RuntimeOrigin::signed(1), root, 0, metadata()),
+      Error::<Test>::EmptyBatch
+    );
+    assert_noop!(
+      PoeModule::anchor_batch(RuntimeOrigin::signed(POOR), root, 3, metadata()),
+      Error::<Test>::InsufficientBalance
+    );
+
+    assert_ok!(PoeModule::anchor_batch(RuntimeOrigin::signed(1), root, 3, metadata()));
+    assert_noop!(
+      PoeModule::anchor_batch(RuntimeOrigin::signed(2), root, 3, metadata()),
+      Error::<Test>::AnchorAlreadyExist
+    );
+  })
+}
+
+#[test]
+fn remove_anchor_works() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let root = merkle::MerkleTree::new(&leaves(3)).unwrap().root();
+    assert_noop!(
+      PoeModule::remove_anchor(RuntimeOrigin::signed(1), root),
+      Error::<Test>::AnchorNotExist
+    );
+
+    assert_ok!(PoeModule::anchor_batch(RuntimeOrigin::signed(1), root, 3, metadata()));
+    assert!(Balances::reserved_balance(1) > 0);
+    assert_noop!(
+      PoeModule::remove_anchor(RuntimeOrigin::signed(2), root),
+      Error::<Test>::NotClaimOwner
+    );
+
+    assert_ok!(PoeModule::remove_anchor(RuntimeOrigin::signed(1), root));
+    assert!(PoeModule::anchors(root).is_none());
+    assert_eq!(Balances::reserved_balance(1), 0);
+    System::assert_last_event(Event::AnchorRemoved(1, root).into());
+
+    // 删除之后可以重新存证
+    assert_ok!(PoeModule::anchor_batch(RuntimeOrigin::signed(2), root, 3, metadata()));
+  })
+}
+
+#[test]
+fn verify_inclusion_works() {
+  new_test_ext().execute_with(|| {
+    let leaves = leaves(4);
+    let tree = merkle::MerkleTree::new(&leaves).unwrap();
+    let proof = tree.proof(1).unwrap();
+
+    // root还没有上链
+    assert!(!PoeModule::verify_inclusion(leaves[1], proof.clone(), tree.root()));
+
+    assert_ok!(PoeModule::anchor_batch(RuntimeOrigin::signed(1), tree.root(), 4, metadata()));
+    assert!(PoeModule::verify_inclusion(leaves[1], proof.clone(), tree.root()));
+    assert!(!PoeModule::verify_inclusion(leaves[2], proof.clone(), tree.root()));
+
+    // 叶子个数和链上记录的不一致
+    let wrong_count = MerkleProof { leaf_count: 5, ..proof };
+    assert!(!PoeModule::verify_inclusion(leaves[1], wrong_count, tree.root()));
+  })
+}
+
+#[test]
+fn verify_inclusion_with_max_leaf_count() {
+  new_test_ext().execute_with(|| {
+    let tree = merkle::MerkleTree::new(&leaves(2)).unwrap();
+    assert_ok!(PoeModule::anchor_batch(RuntimeOrigin::signed(1), tree.root(), u32::MAX, metadata()));
+
+    // 最后一个叶子在每一层都被直接提升，计算宽度时不能溢出
+    let proof = MerkleProof { leaf_index: u32::MAX - 1, leaf_count: u32::MAX, siblings: vec![] };
+    assert!(!merkle::verify(&tree.root(), &leaves(2)[0], &proof));
+    assert!(!PoeModule::verify_inclusion(leaves(2)[0], proof, tree.root()));
+  })
+}
+
+fn cosigners(accounts: &[u64]) -> BoundedVec<u64, <Test as Config>::MaxCosigners> {
+  BoundedVec::try_from(accounts.to_vec()).unwrap()
+}
+
+// 需要2、4、5中threshold个人签名的存证
+fn create_pending(content: &[u8], threshold: u32) -> ContentHash {
+  let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, content);
+  assert_ok!(PoeModule::create_pending_claim(
+    RuntimeOrigin::signed(1),
+    claim_hash,
+    metadata(),
+    None,
+    cosigners(&[2, 4, 5]),
+    threshold
+  ));
+  claim_hash
+}
+
+#[test]
+fn pending_claim_finalized_at_threshold() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    Timestamp::set_timestamp(1_000);
+    let claim_hash = create_pending(b"contract", 2);
+    let deposit = PoeModule::pending_claims(claim_hash).unwrap().deposit;
+    assert_eq!(Balances::reserved_balance(1), deposit);
+
+    assert_ok!(PoeModule::cosign_claim(RuntimeOrigin::signed(4), claim_hash));
+    assert!(!Proofs::<Test>::contains_key(claim_hash));
+    System::assert_last_event(Event::ClaimCosigned(4, claim_hash).into());
+
+    run_to_block(2);
+    assert_ok!(PoeModule::cosign_claim(RuntimeOrigin::signed(2), claim_hash));
+    assert!(PoeModule::pending_claims(claim_hash).is_none());
+    assert_eq!(owner_and_block(claim_hash), Some((1, 2)));
+    // 待签名时的质押转为存证的质押
+    assert_eq!(PoeModule::claim_deposit(claim_hash), deposit);
+    assert_eq!(Balances::reserved_balance(1), deposit);
+    System::assert_last_event(Event::ClaimFinalized(1, claim_hash, 1_000).into());
+  })
+}
+
+#[test]
+fn create_pending_claim_failed() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"contract");
+    let create = |who, expires_at, accounts: &[u64], threshold| {
+      PoeModule::create_pending_claim(
+        RuntimeOrigin::signed(who),
+        claim_hash,
+        metadata(),
+        expires_at,
+        cosigners(accounts),
+        threshold,
+      )
+    };
+
+    assert_noop!(create(1, None, &[2, 4], 0), Error::<Test>::InvalidThreshold);
+    assert_noop!(create(1, None, &[2, 4], 3), Error::<Test>::InvalidThreshold);
+    assert_noop!(create(1, None, &[2, 4, 2], 2), Error::<Test>::DuplicateCosigner);
+    // 超时区块是6，存证最晚在5生效
+    assert_noop!(create(1, Some(5), &[2, 4], 2), Error::<Test>::InvalidExpiry);
+    assert_noop!(create(POOR, None, &[2, 4], 2), Error::<Test>::InsufficientBalance);
+
+    assert_ok!(create(1, Some(6), &[2, 4], 2));
+    assert_noop!(create(1, None, &[2, 4], 2), Error::<Test>::ProofAlreadyExist);
+    // 等待签名的摘要也不能直接创建存证
+    assert_noop!(
+      PoeModule::create_claim_for_hash(RuntimeOrigin::signed(2), claim_hash, metadata(), None),
+      Error::<Test>::ProofAlreadyExist
+    );
+  })
+}
+
+#[test]
+fn create_pending_claim_failed_with_too_many_pending() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    create_pending(b"first", 1);
+    create_pending(b"second", 1);
+
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"third");
+    assert_noop!(
+      PoeModule::create_pending_claim(
+        RuntimeOrigin::signed(1),
+        claim_hash,
+        metadata(),
+        None,
+        cosigners(&[2]),
+        1
+      ),
+      Error::<Test>::TooManyPending
+    );
+  })
+}
+
+#[test]
+fn cosign_claim_failed() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let missing = ContentHash::of(HashAlgorithm::Sha2_256, b"missing");
+    assert_noop!(
+      PoeModule::cosign_claim(RuntimeOrigin::signed(2), missing),
+      Error::<Test>::PendingClaimNotExist
+    );
+
+    let claim_hash = create_pending(b"contract", 3);
+    assert_noop!(
+      PoeModule::cosign_claim(RuntimeOrigin::signed(1), claim_hash),
+      Error::<Test>::NotCosigner
+    );
+
+    assert_ok!(PoeModule::cosign_claim(RuntimeOrigin::signed(2), claim_hash));
+    assert_noop!(
+      PoeModule::cosign_claim(RuntimeOrigin::signed(2), claim_hash),
+      Error::<Test>::AlreadyCosigned
+    );
+    assert_noop!(
+      PoeModule::reject_claim(RuntimeOrigin::signed(2), claim_hash),
+      Error::<Test>::AlreadyCosigned
+    );
+  })
+}
+
+#[test]
+fn reject_claim_works() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_pending(b"contract", 2);
+
+    // 剩下的两个人签名还能达到要求
+    assert_ok!(PoeModule::reject_claim(RuntimeOrigin::signed(4), claim_hash));
+    assert!(PoeModule::pending_claims(claim_hash).is_some());
+    System::assert_last_event(Event::ClaimRejected(4, claim_hash).into());
+
+    assert_ok!(PoeModule::reject_claim(RuntimeOrigin::signed(5), claim_hash));
+    assert!(PoeModule::pending_claims(claim_hash).is_none());
+    assert_eq!(Balances::reserved_balance(1), 0);
+    System::assert_last_event(Event::PendingClaimRejected(1, claim_hash).into());
+  })
+}
+
+#[test]
+fn pending_claim_times_out() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_pending(b"contract", 2);
+    assert_ok!(PoeModule::cosign_claim(RuntimeOrigin::signed(2), claim_hash));
+
+    run_to_block(5);
+    assert!(PoeModule::pending_claims(claim_hash).is_some());
+
+    run_to_block(6);
+    assert!(PoeModule::pending_claims(claim_hash).is_none());
+    assert!(!PendingTimeoutsAt::<Test>::contains_key(6));
+    assert_eq!(Balances::reserved_balance(1), 0);
+    System::assert_last_event(Event::PendingClaimTimedOut(1, claim_hash).into());
+
+    // 超时之后可以重新创建
+    create_pending(b"contract", 2);
+  })
+}
+
+#[test]
+fn transfer_claim_records_history() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+
+    run_to_block(2);
+    assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), hash_of(&claim), 2));
+    System::assert_last_event(Event::ClaimTransferred(1, 2, hash_of(&claim)).into());
+    run_to_block(3);
+    assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(2), hash_of(&claim), 1));
+
+    let record =
+      |owner: u64, block_number: u64, action| CustodyRecord { owner, block_number, action };
+    assert_eq!(
+      PoeModule::custody_history(hash_of(&claim)),
+      vec![
+        record(1, 1, CustodyAction::Created),
+        record(2, 2, CustodyAction::Transferred),
+        record(1, 3, CustodyAction::Transferred),
+      ]
+    );
+
+    // 历史记录满了
+    assert_noop!(
+      PoeModule::transfer_claim(RuntimeOrigin::signed(1), hash_of(&claim), 2),
+      Error::<Test>::HistoryFull
+    );
+
+    // 撤销之后历史记录仍然可以查询
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&claim), 1, reason()));
+    assert_eq!(PoeModule::custody_history(hash_of(&claim)).len(), 3);
+    assert_eq!(Balances::reserved_balance(1), tombstone_deposit(hash_of(&claim)));
+  })
+}
+
+#[test]
+fn finalized_claim_starts_history() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_pending(b"contract", 1);
+
+    run_to_block(2);
+    assert_ok!(PoeModule::cosign_claim(RuntimeOrigin::signed(5), claim_hash));
+
+    let created = CustodyRecord { owner: 1u64, block_number: 2u64, action: CustodyAction::Created };
+    assert_eq!(PoeModule::custody_history(claim_hash), vec![created]);
+  })
+}
+
+#[test]
+fn revoke_claim_leaves_tombstone() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    assert_eq!(PoeModule::claim_status(hash_of(&claim)), None);
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+    assert_eq!(PoeModule::claim_status(hash_of(&claim)), Some(ClaimStatus::Active));
+
+    run_to_block(2);
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&claim), 7, reason()));
+    System::assert_last_event(Event::ClaimRevoked(1, hash_of(&claim), 7).into());
+
+    assert_eq!(
+      PoeModule::tombstones(hash_of(&claim)),
+      Some(Tombstone {
+        owner: 1,
+        block_number: 2,
+        cause: TombstoneCause::Revoked { code: 7, reason: reason() },
+      })
+    );
+    assert_eq!(PoeModule::claim_status(hash_of(&claim)), Some(ClaimStatus::Revoked));
+
+    // 撤销之后默认不能重新创建
+    assert_noop!(
+      PoeModule::create_claim(RuntimeOrigin::signed(2), claim.clone(), metadata(), None),
+      Error::<Test>::ClaimTombstoned
+    );
+    assert_noop!(
+      PoeModule::create_pending_claim(
+        RuntimeOrigin::signed(2),
+        hash_of(&claim),
+        metadata(),
+        None,
+        cosigners(&[1]),
+        1
+      ),
+      Error::<Test>::ClaimTombstoned
+    );
+  })
+}
+
+#[test]
+fn expired_claim_leaves_tombstone() {
+  use frame_support::{traits::Hooks, weights::Weight};
+
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 3);
+
+    // 在区块5才清理，记录的还是过期区块
+    System::set_block_number(5);
+    PoeModule::on_idle(5, Weight::MAX);
+
+    let tombstone = PoeModule::tombstones(claim_hash).unwrap();
+    assert_eq!((tombstone.owner, tombstone.block_number), (1, 3));
+    assert_eq!(tombstone.cause, TombstoneCause::Expired);
+    assert_eq!(PoeModule::claim_status(claim_hash), Some(ClaimStatus::Expired));
+  })
+}
+
+#[test]
+fn expired_claim_status_before_sweep() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 3);
+    System::set_block_number(2);
+    assert_eq!(PoeModule::claim_status(claim_hash), Some(ClaimStatus::Active));
+
+    // 没有执行on_initialize，存证还在Proofs中
+    System::set_block_number(3);
+    assert!(Proofs::<Test>::contains_key(claim_hash));
+    assert_eq!(PoeModule::claim_status(claim_hash), Some(ClaimStatus::Expired));
+  })
+}
+
+#[test]
+fn custody_history_kept_after_revoke() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_expiring(1, b"document", 10);
+    run_to_block(2);
+    assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim_hash, 2));
+    run_to_block(3);
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(2), claim_hash, 1, reason()));
+
+    let record =
+      |owner: u64, block_number: u64, action| CustodyRecord { owner, block_number, action };
+    assert_eq!(
+      PoeModule::custody_history(claim_hash),
+      vec![record(1, 1, CustodyAction::Created), record(2, 2, CustodyAction::Transferred)]
+    );
+    assert_eq!(PoeModule::claim_status(claim_hash), Some(ClaimStatus::Revoked));
+
+    // 取回质押之后历史记录仍然保留
+    assert_ok!(PoeModule::release_tombstone_deposit(RuntimeOrigin::signed(2), claim_hash));
+    assert_eq!(
+      PoeModule::custody_history(claim_hash),
+      vec![record(1, 1, CustodyAction::Created), record(2, 2, CustodyAction::Transferred)]
+    );
+  })
+}
+
+#[test]
+fn release_tombstone_deposit_works() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+    assert_noop!(
+      PoeModule::release_tombstone_deposit(RuntimeOrigin::signed(1), hash_of(&claim)),
+      Error::<Test>::TombstoneNotExist
+    );
+
+    run_to_block(2);
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&claim), 7, reason()));
+    assert!(Balances::reserved_balance(1) > 0);
+    assert_noop!(
+      PoeModule::release_tombstone_deposit(RuntimeOrigin::signed(2), hash_of(&claim)),
+      Error::<Test>::NotClaimOwner
+    );
+
+    assert_ok!(PoeModule::release_tombstone_deposit(RuntimeOrigin::signed(1), hash_of(&claim)));
+    System::assert_last_event(Event::TombstoneDepositReleased(1, hash_of(&claim)).into());
+    assert_eq!(Balances::reserved_balance(1), 0);
+    assert_eq!(PoeModule::claim_deposit(hash_of(&claim)), 0);
+
+    // 只删除撤销原因，仍然可以区分撤销和从来没有存在过
+    assert_eq!(
+      PoeModule::tombstones(hash_of(&claim)),
+      Some(Tombstone {
+        owner: 1,
+        block_number: 2,
+        cause: TombstoneCause::Revoked { code: 7, reason: Default::default() },
+      })
+    );
+    assert_eq!(PoeModule::claim_status(hash_of(&claim)), Some(ClaimStatus::Revoked));
+    assert_noop!(
+      PoeModule::create_claim(RuntimeOrigin::signed(2), claim, metadata(), None),
+      Error::<Test>::ClaimTombstoned
+    );
+  })
+}
+
+#[test]
+fn reclaim_allowed_by_config() {
+  new_test_ext().execute_with(|| {
+    AllowReclaim::set(&true);
+    run_to_block(1);
     let claim = BoundedVec::try_from(vec![0, 1]).unwrap();
-    // let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone(), metadata(), None));
+    assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), hash_of(&claim), 1, reason()));
 
-    assert_noop!(PoeModule::transfer_claim(RuntimeOrigin::signed(1), claim.clone(), 2), Error::<Test>::ClaimNotExist);
+    // 重新创建时删除撤销的记录，退回原来的owner的质押
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(2), claim.clone(), metadata(), None));
+    assert!(PoeModule::tombstones(hash_of(&claim)).is_none());
+    assert_eq!(Balances::reserved_balance(1), 0);
+    let created = CustodyRecord { owner: 2u64, block_number: 1u64, action: CustodyAction::Created };
+    assert_eq!(PoeModule::custody_history(hash_of(&claim)), vec![created]);
+    assert_eq!(PoeModule::claim_status(hash_of(&claim)), Some(ClaimStatus::Active));
   })
-}
\ No newline at end of file
+}
+
+#[test]
+fn pending_claim_status() {
+  new_test_ext().execute_with(|| {
+    run_to_block(1);
+    let claim_hash = create_pending(b"contract", 1);
+    assert_eq!(PoeModule::claim_status(claim_hash), Some(ClaimStatus::Pending));
+
+    assert_ok!(PoeModule::cosign_claim(RuntimeOrigin::signed(2), claim_hash));
+    assert_eq!(PoeModule::claim_status(claim_hash), Some(ClaimStatus::Active));
+  })
+}
+
+#[test]
+fn proof_storage_key_layout() {
+  new_test_ext().execute_with(|| {
+    use sp_core::hashing::{blake2_128, twox_128};
+
+    // 离线校验回执时按照这个格式计算存储key
+    let claim_hash = ContentHash::of(HashAlgorithm::Sha2_256, b"contract");
+    let encoded = claim_hash.encode();
+    let key = [
+      &twox_128(b"PoeModule")[..],
+      &twox_128(b"Proofs")[..],
+      &blake2_128(&encoded)[..],
+      &encoded[..],
+    ]
+    .concat();
+    assert_eq!(PoeModule::proof_storage_key(claim_hash), key);
+  })
+}
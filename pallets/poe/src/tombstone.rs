@@ -0,0 +1,61 @@
+//! 撤销和过期的存证
+//!
+//! 存证失效时删除存证，在`Tombstones`中留下一条记录，验证方可以据此区分存证已经失效和从来
+//! 没有存在过，所有权历史也继续保留。失效的记录和历史记录由失效时的owner质押，owner可以通过
+//! `release_tombstone_deposit`删除撤销原因并取回质押，记录本身不会被删除。
+//! `AllowReclaim`为false时失效的摘要不能再创建存证。
+use crate::*;
+use frame_support::{pallet_prelude::*, traits::ReservableCurrency};
+use sp_runtime::traits::{Saturating, Zero};
+
+impl<T: Config> Pallet<T> {
+	/// 校验可以用claim_hash创建新的存证
+	pub(crate) fn ensure_claimable(claim_hash: &ContentHash) -> DispatchResult {
+		ensure!(!Proofs::<T>::contains_key(claim_hash), Error::<T>::ProofAlreadyExist);
+		// 也不能有相同摘要的存证在等待签名
+		ensure!(!PendingClaims::<T>::contains_key(claim_hash), Error::<T>::ProofAlreadyExist);
+		ensure!(
+			T::AllowReclaim::get() || !Tombstones::<T>::contains_key(claim_hash),
+			Error::<T>::ClaimTombstoned
+		);
+		Ok(())
+	}
+
+	/// 失效的记录需要的质押，按失效的记录和保留的历史记录的字节数计算
+	pub(crate) fn tombstone_deposit(
+		claim_hash: &ContentHash,
+		tombstone: &TombstoneOf<T>,
+	) -> BalanceOf<T> {
+		let history = ClaimHistory::<T>::get(claim_hash);
+		Self::deposit_for_len(tombstone.encoded_size() + history.encoded_size())
+	}
+
+	/// 删除存证，留下失效的记录。存证的质押中保留失效的记录需要的部分，多出的退回owner，
+	/// 不够时尽量补上。过期列表由调用方处理
+	pub(crate) fn bury(claim_hash: ContentHash, tombstone: TombstoneOf<T>) {
+		let required = Self::tombstone_deposit(&claim_hash, &tombstone);
+		let deposit = ClaimDeposits::<T>::get(&claim_hash);
+		let extra = required.saturating_sub(deposit);
+		let held = if extra.is_zero() {
+			T::Currency::unreserve(&tombstone.owner, deposit.saturating_sub(required));
+			required
+		} else if T::Currency::reserve(&tombstone.owner, extra).is_ok() {
+			required
+		} else {
+			// 引入质押之前创建的存证可能没有足够的余额
+			deposit
+		};
+
+		Proofs::<T>::remove(&claim_hash);
+		ClaimDeposits::<T>::insert(&claim_hash, held);
+		Tombstones::<T>::insert(&claim_hash, tombstone);
+	}
+
+	/// 删除失效的记录和保留的历史记录，把质押退回失效时的owner
+	pub(crate) fn unbury(claim_hash: &ContentHash) {
+		if let Some(tombstone) = Tombstones::<T>::take(claim_hash) {
+			ClaimHistory::<T>::remove(claim_hash);
+			T::Currency::unreserve(&tombstone.owner, ClaimDeposits::<T>::take(claim_hash));
+		}
+	}
+}
@@ -0,0 +1,332 @@
+//! Weights for pallet_poe
+//!
+//! This file is a hand-written placeholder, NOT the output of `benchmark pallet`. The values are
+//! estimated from the storage accesses of each call and have not been measured. In particular
+//! the per-byte slope of `create_claim(l)` and the per-claim slope of `expire_claims(n)` are
+//! guesses, not fitted from the benchmarks. Replace the whole file with the generated output
+//! before relying on these weights in production:
+//!
+//! ```text
+//! ./scripts/benchmark.sh poe
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_poe.
+pub trait WeightInfo {
+	fn create_claim(l: u32, ) -> Weight;
+	fn create_claim_for_hash() -> Weight;
+	fn revoke_claim() -> Weight;
+	fn transfer_claim() -> Weight;
+	fn renew_claim() -> Weight;
+	fn expire_claims(n: u32, ) -> Weight;
+	fn anchor_batch() -> Weight;
+	fn remove_anchor() -> Weight;
+	fn release_tombstone_deposit() -> Weight;
+	fn create_pending_claim(s: u32, ) -> Weight;
+	fn cosign_claim(s: u32, ) -> Weight;
+	fn reject_claim(s: u32, ) -> Weight;
+	fn time_out_pending_claims(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_poe using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule PendingClaims (r:1 w:0)
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:0 w:1)
+	/// The `l` parameter is the length of the claim in bytes.
+	fn create_claim(l: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 10_900)
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule PendingClaims (r:1 w:0)
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:0 w:1)
+	fn create_claim_for_hash() -> Weight {
+		Weight::from_parts(44_000_000, 10_900)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:1 w:0)
+	/// Storage: PoeModule Tombstones (r:0 w:1)
+	fn revoke_claim() -> Weight {
+		Weight::from_parts(38_000_000, 8_100)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:1 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn transfer_claim() -> Weight {
+		Weight::from_parts(52_000_000, 12_400)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:2 w:2)
+	fn renew_claim() -> Weight {
+		Weight::from_parts(29_000_000, 7_600)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: PoeModule NextExpiryBlock (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: PoeModule Proofs (r:64 w:64)
+	/// Storage: PoeModule ClaimDeposits (r:64 w:64)
+	/// Storage: System Account (r:64 w:64)
+	/// Storage: PoeModule ClaimHistory (r:64 w:0)
+	/// Storage: PoeModule Tombstones (r:0 w:64)
+	/// The `n` parameter is the number of claims expired and block lists read.
+	fn expire_claims(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 2_300)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3_400).saturating_mul(n.into()))
+	}
+	/// Storage: PoeModule Anchors (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn anchor_batch() -> Weight {
+		Weight::from_parts(36_000_000, 7_900)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: PoeModule Anchors (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn remove_anchor() -> Weight {
+		Weight::from_parts(27_000_000, 7_900)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn release_tombstone_deposit() -> Weight {
+		Weight::from_parts(24_000_000, 7_400)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:0)
+	/// Storage: PoeModule PendingClaims (r:1 w:1)
+	/// Storage: PoeModule Tombstones (r:1 w:0)
+	/// Storage: PoeModule PendingTimeoutsAt (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The `s` parameter is the number of co-signers.
+	fn create_pending_claim(s: u32, ) -> Weight {
+		Weight::from_parts(39_000_000, 9_600)
+			.saturating_add(Weight::from_parts(180_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: PoeModule PendingClaims (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: PoeModule Proofs (r:0 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:0 w:1)
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// The `s` parameter is the number of co-signers.
+	fn cosign_claim(s: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 9_900)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: PoeModule PendingClaims (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The `s` parameter is the number of co-signers.
+	fn reject_claim(s: u32, ) -> Weight {
+		Weight::from_parts(31_000_000, 6_900)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: PoeModule PendingTimeoutsAt (r:1 w:1)
+	/// Storage: PoeModule PendingClaims (r:64 w:64)
+	/// Storage: System Account (r:64 w:64)
+	/// The `n` parameter is the number of timed out pending claims.
+	fn time_out_pending_claims(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1_500)
+			.saturating_add(Weight::from_parts(27_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4_100).saturating_mul(n.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule PendingClaims (r:1 w:0)
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:0 w:1)
+	/// The `l` parameter is the length of the claim in bytes.
+	fn create_claim(l: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 10_900)
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule PendingClaims (r:1 w:0)
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:0 w:1)
+	fn create_claim_for_hash() -> Weight {
+		Weight::from_parts(44_000_000, 10_900)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:1 w:0)
+	/// Storage: PoeModule Tombstones (r:0 w:1)
+	fn revoke_claim() -> Weight {
+		Weight::from_parts(38_000_000, 8_100)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:1 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn transfer_claim() -> Weight {
+		Weight::from_parts(52_000_000, 12_400)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:2 w:2)
+	fn renew_claim() -> Weight {
+		Weight::from_parts(29_000_000, 7_600)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: PoeModule NextExpiryBlock (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: PoeModule Proofs (r:64 w:64)
+	/// Storage: PoeModule ClaimDeposits (r:64 w:64)
+	/// Storage: System Account (r:64 w:64)
+	/// Storage: PoeModule ClaimHistory (r:64 w:0)
+	/// Storage: PoeModule Tombstones (r:0 w:64)
+	/// The `n` parameter is the number of claims expired and block lists read.
+	fn expire_claims(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 2_300)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3_400).saturating_mul(n.into()))
+	}
+	/// Storage: PoeModule Anchors (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn anchor_batch() -> Weight {
+		Weight::from_parts(36_000_000, 7_900)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: PoeModule Anchors (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn remove_anchor() -> Weight {
+		Weight::from_parts(27_000_000, 7_900)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn release_tombstone_deposit() -> Weight {
+		Weight::from_parts(24_000_000, 7_400)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: PoeModule Proofs (r:1 w:0)
+	/// Storage: PoeModule PendingClaims (r:1 w:1)
+	/// Storage: PoeModule Tombstones (r:1 w:0)
+	/// Storage: PoeModule PendingTimeoutsAt (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The `s` parameter is the number of co-signers.
+	fn create_pending_claim(s: u32, ) -> Weight {
+		Weight::from_parts(39_000_000, 9_600)
+			.saturating_add(Weight::from_parts(180_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: PoeModule PendingClaims (r:1 w:1)
+	/// Storage: PoeModule ClaimsExpiringAt (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: PoeModule Proofs (r:0 w:1)
+	/// Storage: PoeModule ClaimDeposits (r:1 w:1)
+	/// Storage: PoeModule ClaimHistory (r:0 w:1)
+	/// Storage: PoeModule Tombstones (r:1 w:1)
+	/// The `s` parameter is the number of co-signers.
+	fn cosign_claim(s: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 9_900)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: PoeModule PendingClaims (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The `s` parameter is the number of co-signers.
+	fn reject_claim(s: u32, ) -> Weight {
+		Weight::from_parts(31_000_000, 6_900)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: PoeModule PendingTimeoutsAt (r:1 w:1)
+	/// Storage: PoeModule PendingClaims (r:64 w:64)
+	/// Storage: System Account (r:64 w:64)
+	/// The `n` parameter is the number of timed out pending claims.
+	fn time_out_pending_claims(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1_500)
+			.saturating_add(Weight::from_parts(27_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4_100).saturating_mul(n.into()))
+	}
+}
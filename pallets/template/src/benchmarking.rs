@@ -6,29 +6,27 @@ use super::*;
 use crate::Pallet as Template;
 use frame_benchmarking::v2::*;
 use frame_system::RawOrigin;
+use sp_std::vec;
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
+	fn submit_data(p: Linear<0, { T::MaxPayloadLen::get() }>) {
 		let caller: T::AccountId = whitelisted_caller();
-		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		let payload = vec![1u8; p as usize].try_into().expect("p is within MaxPayloadLen; qed");
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		#[extrinsic_call]
+		submit_data(RawOrigin::Signed(caller), payload);
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
+	fn extrinsics() {
 		let caller: T::AccountId = whitelisted_caller();
-		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		#[extrinsic_call]
+		extrinsics(RawOrigin::Signed(caller), u64::MAX);
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -8,6 +8,15 @@ pub use pallet::*;
 pub mod weights;
 pub use weights::*;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 use sp_runtime:: { traits::Zero, offchain:: { storage::StorageValueRef, storage::MutateStorageError } };
 
 use serde::{ self, Deserialize, Deserializer};
@@ -102,6 +111,9 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
+		/// submit_data的payload的最大长度
+		#[pallet::constant]
+		type MaxPayloadLen: Get<u32>;
     type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
 	}
 
@@ -131,18 +143,21 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
     
     #[pallet::call_index(2)]
-    #[pallet::weight(0)]
-    pub fn submit_data(origin: OriginFor<T>, payload: Vec<u8>) -> DispatchResultWithPostInfo {
+    #[pallet::weight(T::WeightInfo::submit_data(payload.len() as u32))]
+    pub fn submit_data(
+      origin: OriginFor<T>,
+      payload: BoundedVec<u8, T::MaxPayloadLen>,
+    ) -> DispatchResultWithPostInfo {
       let _who = ensure_signed(origin)?;
       log::info!("OCW ==> in submit_data call: {:?}", payload);
       Ok(().into())
     }
 
     #[pallet::call_index(3)]
-    #[pallet::weight(0)]
+    #[pallet::weight(T::WeightInfo::extrinsics())]
     pub fn extrinsics(origin: OriginFor<T>, number: u64) -> DispatchResultWithPostInfo {
       let who = ensure_signed(origin)?;
-      let key = Self::derive_key(frame_system::Module::<T>::block_number());
+      let key = Self::derive_key(frame_system::Pallet::<T>::block_number());
       let data = IndexingData{ name: b"submit_number_unsigned".to_vec(), number };
       sp_io::offchain_index::set(OFFCHAIN_STORAGE_KEY, &data.encode()); // 向offchain DB storage中写入数据
       log::info!("====write to offchain storage");
@@ -1,27 +1,66 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, weights::WeightInfo};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok, dispatch::GetDispatchInfo, traits::ConstU32, BoundedVec,
+};
+use sp_core::sr25519;
+use sp_runtime::DispatchError;
+
+fn account(id: u8) -> AccountId {
+	sr25519::Public::from_raw([id; 32])
+}
+
+fn payload(len: usize) -> BoundedVec<u8, ConstU32<1024>> {
+	vec![1; len].try_into().unwrap()
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn submit_data_works() {
 	new_test_ext().execute_with(|| {
-		// Go past genesis block so events get deposited
-		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		assert_ok!(TemplateModule::submit_data(RuntimeOrigin::signed(account(1)), payload(3)));
+		// 必须是签名交易
+		assert_noop!(
+			TemplateModule::submit_data(RuntimeOrigin::none(), payload(3)),
+			DispatchError::BadOrigin
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn submit_data_weight_scales_with_payload() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+		let small = RuntimeCall::TemplateModule(crate::Call::submit_data { payload: payload(10) });
+		let large = RuntimeCall::TemplateModule(crate::Call::submit_data { payload: payload(1_000) });
+		assert_eq!(small.get_dispatch_info().weight, <() as WeightInfo>::submit_data(10));
+		assert!(
+			large.get_dispatch_info().weight.ref_time() > small.get_dispatch_info().weight.ref_time()
 		);
 	});
 }
+
+#[test]
+fn submit_data_rejects_oversized_payload() {
+	// 超过MaxPayloadLen的payload无法解码成交易
+	let encoded = crate::Call::<Test>::submit_data { payload: payload(1_024) }.encode();
+	assert!(crate::Call::<Test>::decode(&mut &encoded[..]).is_ok());
+	// call_index是2
+	let oversized = (2u8, vec![1u8; 1_025]).encode();
+	assert!(crate::Call::<Test>::decode(&mut &oversized[..]).is_err());
+}
+
+#[test]
+fn extrinsics_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::extrinsics(RuntimeOrigin::signed(account(1)), 42));
+		assert_noop!(TemplateModule::extrinsics(RuntimeOrigin::none(), 42), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn derive_key_works() {
+	assert_eq!(
+		TemplateModule::derive_key(1),
+		b"node-template::storage::\x01\x00\x00\x00\x00\x00\x00\x00".to_vec()
+	);
+}
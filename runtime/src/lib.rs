@@ -27,7 +27,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
+		StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -104,10 +105,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 300,
+	spec_version: 301,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -149,7 +150,11 @@ parameter_types! { // 这个宏为Get trait做实现
 		::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
 	pub const SS58Prefix: u8 = 42;
 	pub KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub KittyMutationRate: Permill = Permill::from_percent(1);
+	/// 旧版本kitties保存质押的账户，只在迁移中使用
 	pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
+	pub ClaimDepositBase: Balance = EXISTENTIAL_DEPOSIT * 2;
+	pub ClaimDepositPerByte: Balance = EXISTENTIAL_DEPOSIT / 50;
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -255,6 +260,22 @@ impl pallet_balances::Config for Runtime {
 impl pallet_poe::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxClaimLength = ConstU32<512>;
+	type Time = Timestamp;
+	type Currency = Balances;
+	type ClaimDepositBase = ClaimDepositBase;
+	type ClaimDepositPerByte = ClaimDepositPerByte;
+	type MaxDescriptionLength = ConstU32<256>;
+	type MaxMimeTypeLength = ConstU32<64>;
+	type MaxUriLength = ConstU32<256>;
+	type MaxExpiringPerBlock = ConstU32<64>;
+	type ExpiryBatchSize = ConstU32<16>;
+	type MaxCosigners = ConstU32<16>;
+	type PendingClaimTimeout = ConstU32<{ 7 * DAYS }>;
+	type MaxPendingPerBlock = ConstU32<64>;
+	type MaxHistoryLength = ConstU32<64>;
+	type MaxReasonLength = ConstU32<256>;
+	type AllowReclaim = ConstBool<false>;
+	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_kitties::Config for Runtime {
@@ -264,7 +285,13 @@ impl pallet_kitties::Config for Runtime {
 	type Currency = Balances;
 	
 	type KittyPrice = KittyPrice;
-  type PalletId = KittyPalletId;
+	type MaxAuctionsPerBlock = ConstU32<64>;
+	type MaxAuctionDuration = ConstU32<{ 7 * DAYS }>;
+	type MaxKittiesOwned = ConstU32<100>;
+	type MutationRate = KittyMutationRate;
+	type BreedingCooldown = ConstU32<{ 10 * MINUTES }>;
+	type MaxOffspring = ConstU32<10>;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
@@ -344,6 +371,7 @@ RuntimeCall: From<C>,
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+	type MaxPayloadLen = ConstU32<{ 16 * 1024 }>;
 	type AuthorityId = pallet_template::crypto::OcwAuthId;
 }
 
@@ -393,6 +421,13 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations executed on runtime upgrade, in order. Each migration checks the on-chain
+/// storage version of its pallet, so migrations that already ran are skipped.
+pub type Migrations = (
+	pallet_kitties::migrations::Migrations<Runtime, KittyPalletId>,
+	pallet_poe::migrations::Migrations<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -400,6 +435,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -414,6 +450,8 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
+		[pallet_poe, PoeModule]
+		[pallet_kitties, KittiesModule]
 	);
 }
 
@@ -541,6 +579,56 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn kitty(
+			kitty_id: pallet_kitties::KittyId,
+		) -> Option<pallet_kitties::KittyDetails<AccountId, Balance, BlockNumber>> {
+			KittiesModule::kitty_details(kitty_id)
+		}
+
+		fn kitties_of(
+			owner: AccountId,
+		) -> Vec<pallet_kitties::KittyDetails<AccountId, Balance, BlockNumber>> {
+			KittiesModule::kitties_of(&owner)
+		}
+
+		fn listings(
+		) -> Vec<(pallet_kitties::KittyId, AccountId, pallet_kitties::Listing<Balance, BlockNumber>)> {
+			KittiesModule::listings()
+		}
+
+		fn family_tree(
+			kitty_id: pallet_kitties::KittyId,
+			generations: u32,
+		) -> Vec<pallet_kitties::FamilyMember> {
+			KittiesModule::family_tree(kitty_id, generations)
+		}
+	}
+
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber> for Runtime {
+		fn verify_inclusion(
+			leaf: sp_core::H256,
+			proof: pallet_poe::MerkleProof,
+			root: sp_core::H256,
+		) -> bool {
+			PoeModule::verify_inclusion(leaf, proof, root)
+		}
+
+		fn custody_history(
+			claim_hash: pallet_poe::ContentHash,
+		) -> Vec<pallet_poe::CustodyRecord<AccountId, BlockNumber>> {
+			PoeModule::custody_history(claim_hash)
+		}
+
+		fn claim_status(claim_hash: pallet_poe::ContentHash) -> Option<pallet_poe::ClaimStatus> {
+			PoeModule::claim_status(claim_hash)
+		}
+
+		fn proof_storage_key(claim_hash: pallet_poe::ContentHash) -> Vec<u8> {
+			PoeModule::proof_storage_key(claim_hash)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+# 运行pallet的benchmark并生成weights.rs
+#
+# 用法: ./scripts/benchmark.sh <pallet名字>
+#   例如 ./scripts/benchmark.sh kitties，生成的结果写入pallets/kitties/src/weights.rs
+#   需要在和出块节点相同规格的机器上运行
+set -e
+
+PALLET=${1:?usage: $0 <kitties|poe|template>}
+NODE=./target/release/node-template
+
+echo "*** Building node with runtime-benchmarks enabled"
+cargo build --release --features runtime-benchmarks
+
+echo "*** Benchmarking pallet_$PALLET"
+$NODE benchmark pallet --chain dev --pallet "pallet_$PALLET" \
+   --extrinsic '*' --steps 50 --repeat 20 --wasm-execution compiled \
+   --output "pallets/$PALLET/src/weights.rs"
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+# 用本地节点的状态快照演练runtime升级
+#
+# 用法: ./scripts/try-runtime.sh [节点ws地址] [快照文件]
+#   节点ws地址默认为ws://127.0.0.1:9944，快照文件默认为kitties.snap
+#   快照文件已经存在时直接使用，不再重新下载
+set -e
+
+URI=${1:-ws://127.0.0.1:9944}
+SNAPSHOT=${2:-kitties.snap}
+NODE=./target/release/node-template
+RUNTIME=./target/release/wbuild/node-template-runtime/node_template_runtime.compact.compressed.wasm
+
+echo "*** Building node and runtime with try-runtime enabled"
+cargo build --release --features try-runtime
+
+if [ ! -f "$SNAPSHOT" ]; then
+   echo "*** Creating state snapshot from $URI"
+   $NODE try-runtime --runtime existing create-snapshot --uri "$URI" "$SNAPSHOT"
+fi
+
+echo "*** Running on-runtime-upgrade against $SNAPSHOT"
+RUST_LOG=runtime=debug,try-runtime::cli=info \
+   $NODE try-runtime --runtime "$RUNTIME" on-runtime-upgrade --checks=pre-and-post \
+   snap --snapshot-path "$SNAPSHOT"